@@ -0,0 +1,56 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha12Rng;
+
+use application::{
+    point::Point2d,
+    unit::{Wall, Walls},
+};
+
+const SIZE: u16 = 1000;
+const LOOKUPS: usize = 100;
+
+/// The surrounding walls of a `SIZE` x `SIZE` map plus `n_random` walls
+/// inside of it.
+fn walls(n_random: usize, rng: &mut impl Rng) -> Vec<Wall> {
+    let mut walls = vec![];
+    for i in 0..SIZE {
+        walls.push(Wall::new(i, 0));
+        walls.push(Wall::new(i, SIZE - 1));
+        walls.push(Wall::new(0, i));
+        walls.push(Wall::new(SIZE - 1, i));
+    }
+    for _ in 0..n_random {
+        walls.push(Wall::new(rng.gen_range(1..SIZE - 1), rng.gen_range(1..SIZE - 1)));
+    }
+    walls
+}
+
+fn collision_lookups(c: &mut Criterion) {
+    let mut rng = ChaCha12Rng::seed_from_u64(0);
+    let tiles: Vec<Point2d<u16>> = (0..LOOKUPS)
+        .map(|_| Point2d::new(rng.gen_range(0..SIZE), rng.gen_range(0..SIZE)))
+        .collect();
+
+    let mut group = c.benchmark_group("wall collision on a 1000x1000 map");
+    for n_random in [1_000, 10_000, 100_000] {
+        let list = walls(n_random, &mut rng);
+        let index = Walls::from(list.clone());
+
+        group.bench_with_input(BenchmarkId::new("linear scan", n_random), &list, |b, list| {
+            b.iter(|| {
+                tiles
+                    .iter()
+                    .filter(|tile| list.iter().any(|wall| *wall == Wall::new(tile.x, tile.y)))
+                    .count()
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("index", n_random), &index, |b, index| {
+            b.iter(|| tiles.iter().filter(|tile| index.contains(black_box(**tile))).count())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, collision_lookups);
+criterion_main!(benches);
@@ -0,0 +1,304 @@
+mod tests;
+
+use std::{error::Error, path::Path, time::Duration};
+
+use clap::{error::ErrorKind, CommandFactory, Parser, ValueEnum};
+use crossterm::terminal;
+
+use crate::{
+    game::{Game, GameBuilder},
+    input::{bindings::MAX_PLAYERS, Bindings, ControlScheme},
+    level::Level,
+    map::{Caves, MapGenerator, Maze, Rooms},
+    scores::HighScores,
+    ui::Theme,
+    unit::Enemy,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum MapKind {
+    /// A maze carved by a recursive backtracker
+    Maze,
+    /// Organic caves grown by a cellular automaton
+    Caves,
+    /// Rooms connected by corridors
+    Rooms,
+}
+
+impl MapKind {
+    pub fn generator(self) -> Box<dyn MapGenerator> {
+        match self {
+            MapKind::Maze => Box::new(Maze),
+            MapKind::Caves => Box::new(Caves::default()),
+            MapKind::Rooms => Box::new(Rooms::default()),
+        }
+    }
+}
+
+/// Collect the coins and stay away from the enemies.
+///
+/// Steer with the left and right arrows, change speed with up and down,
+/// pause with `p`, save with Ctrl-S and give up with `q`. WASD and the vim
+/// keys steer as well, and every key can be rebound with `--keys`. With
+/// `--controls direct`, the player moves the way the held keys point.
+#[derive(Debug, Parser)]
+#[command(version, about)]
+pub struct Cli {
+    /// Load a hand-authored map instead of generating one. See the README
+    /// for the format
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["width", "height", "walls"])]
+    pub level: Option<String>,
+
+    /// Generate the walls instead of placing them at random
+    #[arg(long, conflicts_with = "level")]
+    pub map: Option<MapKind>,
+
+    /// Width of the game area, including the surrounding walls
+    #[arg(long, default_value_t = 80, value_parser = clap::value_parser!(u16).range(3..))]
+    pub width: u16,
+
+    /// Height of the game area, including the surrounding walls
+    #[arg(long, default_value_t = 40, value_parser = clap::value_parser!(u16).range(3..))]
+    pub height: u16,
+
+    /// Number of walls placed at random positions
+    #[arg(long, default_value_t = 30)]
+    pub walls: u16,
+
+    /// Number of collectibles in the game at the same time
+    #[arg(long, default_value_t = 3, value_parser = clap::value_parser!(u16).range(1..))]
+    pub collectibles: u16,
+
+    /// Health the player starts with
+    #[arg(long, default_value_t = 10, value_parser = clap::value_parser!(u8).range(1..))]
+    pub health: u8,
+
+    /// Number of players sharing the keyboard. Each moves with their own
+    /// keys: WASD, the arrows, the vim keys and the number keys
+    #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u8).range(1..=MAX_PLAYERS as i64))]
+    pub players: u8,
+
+    /// How the movement keys steer the players
+    #[arg(long, value_enum, default_value_t = ControlScheme::Tank)]
+    pub controls: ControlScheme,
+
+    /// How far the player can move in the middle of the screen before a map
+    /// that does not fit on it scrolls along
+    #[arg(long, value_name = "COLSxROWS", default_value = "16x8", value_parser = parse_size)]
+    pub dead_zone: (u16, u16),
+
+    /// Keep scrolling past the edges of a map that does not fit on the
+    /// screen, so the player stays near the middle
+    #[arg(long)]
+    pub no_clamp: bool,
+
+    /// Number of updates the player can not be hurt after an enemy hit
+    #[arg(long, value_name = "TICKS", default_value_t = 10)]
+    pub invulnerability: u32,
+
+    /// Number of tiles an enemy hit pushes the player away
+    #[arg(long, value_name = "TILES", default_value_t = 2.0, value_parser = parse_non_negative)]
+    pub knockback: f64,
+
+    /// Speed the player starts with, in tiles per second
+    #[arg(long, default_value_t = 2.0, value_parser = parse_non_negative)]
+    pub speed: f64,
+
+    /// Comma separated speeds of the enemies, one enemy per speed
+    #[arg(
+        long = "enemies",
+        value_name = "SPEEDS",
+        value_delimiter = ',',
+        default_values_t = [0.1, 0.2, 0.3, 0.4, 0.5, 0.6, 0.7, 0.8, 0.9],
+        value_parser = parse_non_negative,
+    )]
+    pub enemy_speeds: Vec<f64>,
+
+    /// Time between two updates, in milliseconds
+    #[arg(long, default_value_t = 280, value_parser = clap::value_parser!(u64).range(1..))]
+    pub tick: u64,
+
+    /// Frames drawn per second, independent of the time between updates
+    #[arg(long, default_value_t = 30, value_parser = clap::value_parser!(u32).range(1..))]
+    pub fps: u32,
+
+    /// Seed of the random number generator, random if omitted
+    #[arg(long)]
+    pub seed: Option<u64>,
+
+    /// Where the game is saved when pressing the save key (Ctrl+s by
+    /// default)
+    #[arg(long, value_name = "FILE", default_value = "savegame.json")]
+    pub save_path: String,
+
+    /// Where the high scores are kept, by default in the data directory of
+    /// the user
+    #[arg(long, value_name = "FILE")]
+    pub scores: Option<String>,
+
+    /// Key bindings to use instead of the defaults, by default
+    /// `hide-n-seek/keys.json` in the config directory of the user if it
+    /// exists. See the README for the format
+    #[arg(long, value_name = "FILE")]
+    pub keys: Option<String>,
+
+    /// Glyphs and colours to draw with: `unicode`, `ascii`, `high-contrast`
+    /// or a theme file. By default `hide-n-seek/theme.json` in the config
+    /// directory of the user if it exists, or `ascii` if the locale is not
+    /// UTF-8. See the README for the format
+    #[arg(long, value_name = "NAME|FILE")]
+    pub theme: Option<String>,
+
+    /// Record the session to a replay file. The games after a restart are
+    /// recorded next to it, e.g. to replay-2.json and replay-3.json for
+    /// replay.json
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["replay", "resume"])]
+    pub record: Option<String>,
+
+    /// Play a recorded session back. The other options must match the
+    /// ones the session was recorded with
+    #[arg(long, value_name = "FILE", conflicts_with = "resume")]
+    pub replay: Option<String>,
+
+    /// Continue a saved game, which is saved to FILE again. Only `--keys`,
+    /// `--theme` and `--scores` apply to it, the other options only to the
+    /// games after a restart
+    #[arg(long, value_name = "FILE")]
+    pub resume: Option<String>,
+}
+
+fn parse_non_negative(value: &str) -> Result<f64, String> {
+    let number: f64 = value.parse().map_err(|_| format!("`{}` is not a number", value))?;
+    if number.is_finite() && number >= 0.0 {
+        Ok(number)
+    } else {
+        Err(format!("`{}` must be a finite number of at least 0", value))
+    }
+}
+
+fn parse_size(value: &str) -> Result<(u16, u16), String> {
+    let error = || format!("`{}` is not a size like 16x8", value);
+    let (columns, rows) = value.split_once('x').ok_or_else(error)?;
+    Ok((
+        columns.parse().map_err(|_| error())?,
+        rows.parse().map_err(|_| error())?,
+    ))
+}
+
+impl Cli {
+    /// Parses the command line and exits with a usage message if it is
+    /// invalid.
+    pub fn parse_and_validate() -> Self {
+        let cli = Self::parse();
+        if let Err(error) = cli.validate() {
+            error.exit();
+        }
+        cli
+    }
+
+    /// Checks the constraints between options that clap can not express.
+    pub fn validate(&self) -> Result<(), clap::Error> {
+        let inner_tiles = (self.width as u32 - 2) * (self.height as u32 - 2);
+        if self.walls as u32 >= inner_tiles {
+            return Err(Cli::command().error(
+                ErrorKind::ValueValidation,
+                format!(
+                    "{} walls do not fit in a {}x{} game, there are only {} free tiles",
+                    self.walls, self.width, self.height, inner_tiles
+                ),
+            ));
+        }
+        Ok(())
+    }
+
+    /// The key bindings of each of the `players` from `--keys`, or from the
+    /// default file if there is one.
+    pub fn key_bindings(&self, players: usize) -> Result<Vec<Bindings>, Box<dyn Error>> {
+        match &self.keys {
+            Some(path) => Ok(
+                Bindings::load(path, players).map_err(|error| format!("{}: {}", path, error))?
+            ),
+            None => Bindings::load_default(players),
+        }
+    }
+
+    /// Where game number `game` of the session, counting from 0, is
+    /// recorded with `--record`.
+    pub fn record_path(&self, game: usize) -> Option<String> {
+        let path = Path::new(self.record.as_ref()?);
+        if game == 0 {
+            return Some(path.to_string_lossy().into_owned());
+        }
+        let mut name = path.file_stem().unwrap_or_default().to_os_string();
+        name.push(format!("-{}", game + 1));
+        if let Some(extension) = path.extension() {
+            name.push(".");
+            name.push(extension);
+        }
+        Some(path.with_file_name(name).to_string_lossy().into_owned())
+    }
+
+    /// The theme from `--theme`, or the default one.
+    pub fn theme(&self) -> Result<Theme, Box<dyn Error>> {
+        match &self.theme {
+            Some(name) => Ok(Theme::find(name).map_err(|error| format!("{}: {}", name, error))?),
+            None => Theme::load_default(),
+        }
+    }
+
+    /// Where the high scores are kept, from `--scores` or else in the data
+    /// directory.
+    pub fn high_scores_path(&self) -> Option<String> {
+        self.scores.clone().or_else(|| {
+            HighScores::default_path().and_then(|path| path.to_str().map(str::to_string))
+        })
+    }
+
+    pub fn game_builder(&self) -> Result<GameBuilder, Box<dyn Error>> {
+        let mut builder = Game::builder()
+            .width(self.width)
+            .height(self.height)
+            .n_random_walls(self.walls)
+            .n_collectibles(self.collectibles)
+            .player_starting_health(self.health)
+            .player_starting_speed(self.speed)
+            .control_scheme(self.controls)
+            .player_invulnerability(self.invulnerability)
+            .knockback(self.knockback)
+            .enemies(
+                self.enemy_speeds
+                    .iter()
+                    .map(|speed| Enemy::with_speed(*speed))
+                    .collect(),
+            )
+            .update_interval(Duration::from_millis(self.tick))
+            .frame_interval(Duration::from_secs(1) / self.fps)
+            .save_path(&self.save_path)
+            .n_players(self.players.into())
+            .key_bindings(self.key_bindings(self.players.into())?)
+            .camera_dead_zone(self.dead_zone.0, self.dead_zone.1)
+            .camera_clamping(!self.no_clamp)
+            .theme(self.theme()?);
+
+        if let Ok((columns, rows)) = terminal::size() {
+            builder = builder.screen_size(columns, rows);
+        }
+        if let Some(map) = self.map {
+            let name = map.to_possible_value().expect("no map kind is skipped");
+            builder = builder
+                .map_generator(map.generator())
+                .map_name(name.get_name());
+        }
+        if let Some(path) = &self.level {
+            let level = Level::load(path).map_err(|error| format!("{}: {}", path, error))?;
+            builder = builder.level(&level).map_name(path);
+        }
+        if let Some(path) = self.high_scores_path() {
+            builder = builder.high_scores_path(&path);
+        }
+        if let Some(seed) = self.seed {
+            builder = builder.seed(seed);
+        }
+        Ok(builder)
+    }
+}
@@ -0,0 +1,59 @@
+#![cfg(test)]
+
+use clap::{error::ErrorKind, CommandFactory, Parser};
+
+use super::Cli;
+
+#[test]
+fn test_cli_definition() {
+    Cli::command().debug_assert();
+}
+
+#[test]
+fn test_cli_parses_game_options() {
+    let cli = Cli::try_parse_from([
+        "hide-n-seek",
+        "--width",
+        "30",
+        "--enemies",
+        "0.5,1.5",
+        "--seed",
+        "9",
+        "--dead-zone",
+        "20x6",
+    ])
+    .unwrap();
+    assert!(cli.validate().is_ok());
+    assert_eq!(cli.width, 30);
+    assert_eq!(cli.enemy_speeds, vec![0.5, 1.5]);
+    assert_eq!(cli.seed, Some(9));
+    assert_eq!(cli.dead_zone, (20, 6));
+}
+
+#[test]
+fn test_cli_rejects_invalid_options() {
+    let error = Cli::try_parse_from(["hide-n-seek", "--width", "2"]).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::ValueValidation);
+
+    let error = Cli::try_parse_from(["hide-n-seek", "--enemies", "0.5,fast"]).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::ValueValidation);
+
+    let error = Cli::try_parse_from(["hide-n-seek", "--dead-zone", "20"]).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::ValueValidation);
+
+    let cli = Cli::try_parse_from(["hide-n-seek", "--width", "4", "--height", "4"]).unwrap();
+    assert_eq!(cli.validate().unwrap_err().kind(), ErrorKind::ValueValidation);
+}
+
+#[test]
+fn test_games_after_a_restart_are_recorded_next_to_the_first() {
+    let cli = Cli::try_parse_from(["hide-n-seek", "--record", "runs/replay.json"]).unwrap();
+    assert_eq!(cli.record_path(0).unwrap(), "runs/replay.json");
+    assert_eq!(cli.record_path(2).unwrap(), "runs/replay-3.json");
+
+    let cli = Cli::try_parse_from(["hide-n-seek", "--record", "replay"]).unwrap();
+    assert_eq!(cli.record_path(1).unwrap(), "replay-2");
+
+    let cli = Cli::try_parse_from(["hide-n-seek"]).unwrap();
+    assert_eq!(cli.record_path(0), None);
+}
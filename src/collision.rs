@@ -0,0 +1,115 @@
+mod tests;
+
+use crate::point::Point2d;
+
+/// How far a unit stops in front of a wall it runs into. Rounding its
+/// position has to give the tile in front of the wall, not the wall.
+const CLEARANCE: f64 = 1e-6;
+
+/// The first blocked tile on a movement.
+struct Hit {
+    /// How far along the movement the blocked tile is entered, from 0 to 1.
+    fraction: f64,
+    /// Whether the tile is entered through its left or right side.
+    along_x: bool,
+    /// Where the unit stops, inside the last free tile.
+    stop: Point2d<f64>,
+}
+
+/// The tile a position belongs to. Tile `n` spans from `n - 0.5` to
+/// `n + 0.5`, like rounding the position does.
+fn tile(coordinate: f64) -> i64 {
+    (coordinate + 0.5).floor() as i64
+}
+
+/// Moves `coordinate` inside of tile `tile`, keeping clear of its borders.
+fn clamp_to_tile(coordinate: f64, tile: i64) -> f64 {
+    let center = tile as f64;
+    coordinate.clamp(center - 0.5 + CLEARANCE, center + 0.5 - CLEARANCE)
+}
+
+/// The direction of the steps along one axis, the fraction of the movement
+/// it takes to cross a whole tile and the fraction at which the first tile
+/// border is crossed.
+fn axis(start: f64, distance: f64) -> (i64, f64, f64) {
+    let border = tile(start) as f64;
+    if distance > 0.0 {
+        (1, 1.0 / distance, (border + 0.5 - start) / distance)
+    } else if distance < 0.0 {
+        (-1, -1.0 / distance, (start - border + 0.5) / -distance)
+    } else {
+        (0, f64::INFINITY, f64::INFINITY)
+    }
+}
+
+/// Walks the tiles the movement passes through with a DDA grid traversal
+/// and returns the first one that is blocked. The tile the movement starts
+/// on is not checked. When the movement passes exactly through a corner,
+/// the tile beside it on the x axis is checked first, so a unit can not
+/// squeeze diagonally between two walls.
+fn first_hit(
+    from: Point2d<f64>,
+    movement: Point2d<f64>,
+    is_blocked: &impl Fn(Point2d<u16>) -> bool,
+) -> Option<Hit> {
+    let blocked = |x: i64, y: i64| match (u16::try_from(x), u16::try_from(y)) {
+        (Ok(x), Ok(y)) => is_blocked(Point2d::new(x, y)),
+        _ => true,
+    };
+
+    let (mut x, mut y) = (tile(from.x), tile(from.y));
+    let (step_x, delta_x, mut next_x) = axis(from.x, movement.x);
+    let (step_y, delta_y, mut next_y) = axis(from.y, movement.y);
+
+    loop {
+        let along_x = next_x <= next_y;
+        let fraction = next_x.min(next_y);
+        if fraction > 1.0 {
+            return None;
+        }
+
+        let reached = from + movement * fraction;
+        let stop = Point2d::new(clamp_to_tile(reached.x, x), clamp_to_tile(reached.y, y));
+        if along_x {
+            x += step_x;
+            next_x += delta_x;
+        } else {
+            y += step_y;
+            next_y += delta_y;
+        }
+
+        if blocked(x, y) {
+            return Some(Hit {
+                fraction,
+                along_x,
+                stop,
+            });
+        }
+    }
+}
+
+/// Where a unit at `from` ends up after moving by `movement` without passing
+/// through the tiles for which `is_blocked` is true, no matter how long the
+/// movement is. When a wall is in the way the unit stops in front of it and
+/// slides along it with the rest of the movement that is parallel to the
+/// wall. Tiles outside of the `u16` range are blocked.
+pub fn slide(
+    from: Point2d<f64>,
+    movement: Point2d<f64>,
+    is_blocked: impl Fn(Point2d<u16>) -> bool,
+) -> Point2d<f64> {
+    let mut position = from;
+    let mut movement = movement;
+    // every hit cancels the movement along one axis, so this ends after at
+    // most two hits
+    while let Some(hit) = first_hit(position, movement, &is_blocked) {
+        let rest = movement * (1.0 - hit.fraction);
+        position = hit.stop;
+        if hit.along_x {
+            movement = Point2d::new(0.0, rest.y);
+        } else {
+            movement = Point2d::new(rest.x, 0.0);
+        }
+    }
+    position + movement
+}
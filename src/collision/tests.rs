@@ -0,0 +1,62 @@
+#![cfg(test)]
+
+use crate::point::Point2d;
+
+use super::*;
+
+fn assert_close(actual: Point2d<f64>, expected: Point2d<f64>) {
+    assert!(
+        actual.distance(&expected) < 1e-3,
+        "{:?} is not close to {:?}",
+        actual,
+        expected
+    );
+}
+
+#[test]
+fn test_free_movement_is_not_changed() {
+    let is_blocked = |position: Point2d<u16>| position == Point2d::new(9, 9);
+    let from = Point2d::new(2.0, 2.0);
+    let movement = Point2d::new(3.3, -1.2);
+
+    assert_close(slide(from, movement, is_blocked), from + movement);
+    assert_close(slide(from, Point2d::new(0.0, 0.0), is_blocked), from);
+}
+
+#[test]
+fn test_fast_movement_does_not_tunnel_through_a_wall() {
+    let is_blocked = |position: Point2d<u16>| position.x == 5;
+
+    let stopped = slide(Point2d::new(2.0, 3.0), Point2d::new(10.0, 0.0), is_blocked);
+    assert_close(stopped, Point2d::new(4.5, 3.0));
+    assert_eq!(stopped.round().to_u16(), Point2d::new(4, 3));
+
+    let stopped = slide(Point2d::new(8.0, 3.0), Point2d::new(-10.0, 0.0), is_blocked);
+    assert_eq!(stopped.round().to_u16(), Point2d::new(6, 3));
+}
+
+#[test]
+fn test_movement_slides_along_walls() {
+    let is_blocked = |position: Point2d<u16>| position.y == 0;
+
+    let slid = slide(Point2d::new(3.0, 2.0), Point2d::new(4.0, -4.0), is_blocked);
+    assert_close(slid, Point2d::new(7.0, 0.5));
+    assert_eq!(slid.round().to_u16(), Point2d::new(7, 1));
+}
+
+#[test]
+fn test_movement_stops_in_a_corner() {
+    let is_blocked = |position: Point2d<u16>| position.x == 4 || position.y == 4;
+
+    let stopped = slide(Point2d::new(2.0, 2.0), Point2d::new(5.0, 6.0), is_blocked);
+    assert_eq!(stopped.round().to_u16(), Point2d::new(3, 3));
+}
+
+#[test]
+fn test_movement_does_not_squeeze_between_diagonal_walls() {
+    let is_blocked =
+        |position: Point2d<u16>| position == Point2d::new(3, 2) || position == Point2d::new(2, 3);
+
+    let stopped = slide(Point2d::new(2.0, 2.0), Point2d::new(3.0, 3.0), is_blocked);
+    assert_eq!(stopped.round().to_u16(), Point2d::new(2, 2));
+}
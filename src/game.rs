@@ -1,29 +1,67 @@
 mod builder;
+mod replay;
+mod state;
 mod tests;
+mod timestep;
 
 use std::{
+    any::Any,
     cell::RefCell,
-    io::{stdout, Stdout, Write},
-    time::Duration,
+    collections::{HashSet, VecDeque},
+    error::Error,
+    time::{Duration, Instant},
 };
 
-use builder::GameBuilder;
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
 use derivative::Derivative;
-use rand::RngCore;
+use rand::{distributions::WeightedIndex, prelude::Distribution, Rng};
+use rand_chacha::ChaCha12Rng;
 
 use serde::{Deserialize, Serialize};
 
 use crate::{
+    common::JsonIo,
     hud::Hud,
-    input,
+    collision,
+    input::{self, Action, Bindings, ControlScheme, HeldKeys},
+    pathfinding,
     point::Point2d,
+    scores::{HighScore, HighScores},
+    visibility,
     traits::*,
-    ui::{draw::*, UI},
-    unit::{Collectible, Enemy, Player, PlayerState, Wall},
+    ui::{draw::*, terminal, Camera, Renderer, Screen, Theme, UI},
+    unit::{Collectible, CollectibleKind, Enemy, Player, PlayerState, Wall, Walls},
 };
 
-fn rng_new() -> RefCell<Box<dyn RngCore>> {
-    RefCell::new(Box::new(rand::thread_rng()))
+pub use builder::GameBuilder;
+pub use replay::{Replay, ReplayEvent};
+pub use state::{GameState, RunOutcome, StateEvent};
+pub use timestep::FixedTimestep;
+
+fn save_path_default() -> String {
+    "savegame.json".to_string()
+}
+
+/// How many characters the name of a high score can have.
+const MAX_NAME_LENGTH: usize = 12;
+
+fn frame_interval_default() -> Duration {
+    Duration::from_secs(1) / 30
+}
+
+fn bindings_default() -> Vec<Bindings> {
+    Bindings::defaults(1)
+}
+
+/// Rows between the map and the HUD line of the first player.
+const HUD_MARGIN: u16 = 2;
+
+/// The smallest part of a map the screen has to show. Smaller maps have to
+/// fit as a whole.
+const MIN_VIEW: Point2d<u16> = Point2d { x: 40, y: 20 };
+
+fn renderer_new() -> Box<dyn Renderer> {
+    Box::new(UI::new())
 }
 
 #[derive(Derivative, Serialize, Deserialize)]
@@ -31,20 +69,69 @@ fn rng_new() -> RefCell<Box<dyn RngCore>> {
 pub struct Game {
     height: u16,
     width: u16,
-    #[serde(skip, default = "stdout")]
-    stdout: Stdout,
     enemies: RefCell<Vec<Enemy>>,
+    enemy_spawns: Vec<Point2d<u16>>,
+    collectible_spawn: Option<Point2d<u16>>,
     n_random_walls: u16,
-    walls: Vec<Wall>,
-    collectible: RefCell<Collectible>,
-    player_movement: RefCell<Player>,
-    player_state: RefCell<PlayerState>,
-    #[serde(skip, default = "crate::ui::UI::new")]
-    ui: UI,
-    #[serde(skip, default = "rng_new")]
+    walls: Walls,
+    collectibles: RefCell<Vec<Collectible>>,
+    /// How often each kind of collectible spawns relative to the others.
+    collectible_weights: Vec<(CollectibleKind, u32)>,
+    players: Vec<RefCell<Player>>,
+    /// The state of each player, in the same order as `players`.
+    player_states: Vec<RefCell<PlayerState>>,
+    /// How many tiles an enemy hit pushes the player away.
+    knockback: f64,
+    #[serde(skip, default = "renderer_new")]
+    #[derivative(Debug = "ignore")]
+    renderer: Box<dyn Renderer>,
     #[derivative(Debug = "ignore")]
-    rng: RefCell<Box<dyn RngCore>>,
+    rng: RefCell<ChaCha12Rng>,
+    seed: u64,
+    tick: u64,
+    #[derivative(Debug = "ignore")]
+    recorded_events: Vec<ReplayEvent>,
+    #[serde(skip, default = "save_path_default")]
+    save_path: String,
     pub update_interval_millis: Box<Duration>,
+    /// Time between two frames, independent of the update interval.
+    #[serde(skip, default = "frame_interval_default")]
+    frame_interval: Duration,
+    /// Where the player and then each enemy was before the last update, to
+    /// draw them in between two updates.
+    #[serde(skip)]
+    #[derivative(Debug = "ignore")]
+    previous_positions: Vec<Point2d<f64>>,
+    #[serde(skip)]
+    state: GameState,
+    /// Which part of the map is shown, for maps that do not fit on the
+    /// screen.
+    #[serde(skip)]
+    camera: Camera,
+    /// The columns and rows of the screen the game is drawn on.
+    #[serde(skip)]
+    screen_size: Point2d<u16>,
+    /// The glyphs and colours the game is drawn with.
+    #[serde(skip)]
+    #[derivative(Debug = "ignore")]
+    theme: Theme,
+    /// The keys of each player.
+    #[serde(skip, default = "bindings_default")]
+    #[derivative(Debug = "ignore")]
+    bindings: Vec<Bindings>,
+    #[serde(skip)]
+    #[derivative(Debug = "ignore")]
+    held_keys: Vec<HeldKeys>,
+    /// The map the game is played on, which is kept with its high score.
+    map_name: String,
+    /// Where the high scores are kept, none if they are not.
+    #[serde(skip)]
+    high_scores_path: Option<String>,
+    #[serde(skip)]
+    high_scores: HighScores,
+    /// The name typed so far, while a new high score is entered.
+    #[serde(skip)]
+    name_entry: Option<String>,
     hud: RefCell<Hud>,
 }
 
@@ -55,8 +142,14 @@ impl PartialEq for Game {
             && self.enemies == other.enemies
             && self.n_random_walls == other.n_random_walls
             && self.walls == other.walls
-            && self.collectible == other.collectible
-            && self.player_movement == other.player_movement
+            && self.collectibles == other.collectibles
+            && self.collectible_weights == other.collectible_weights
+            && self.players == other.players
+            && self.player_states == other.player_states
+            && self.knockback == other.knockback
+            && self.rng == other.rng
+            && self.seed == other.seed
+            && self.tick == other.tick
             && self.update_interval_millis == other.update_interval_millis
     }
 }
@@ -70,17 +163,208 @@ impl Game {
         GameBuilder::new()
     }
 
-    pub fn player_state(&self) -> &RefCell<PlayerState> {
-        &self.player_state
+    /// Loads a game saved with `save`. Saving again writes back to `path`.
+    pub fn load(path: &str) -> Result<Self, Box<dyn Error>> {
+        let mut game: Self = JsonIo::new(path).read_json()?;
+        game.save_path = path.to_string();
+        game.set_screen_size(game.width, game.height + game.hud_height());
+        Ok(game)
     }
 
-    pub fn player_position(&self) -> Point2d<f64> {
-        self.player_movement.borrow().position()
+    pub fn save(&self, path: &str) -> Result<(), Box<dyn Error>> {
+        JsonIo::new(path).write_json(self)
     }
 
-    pub fn init(&mut self) {
-        self.ui.prepare();
+    pub fn state(&self) -> GameState {
+        self.state
+    }
+
+    /// Moves to `state`, loading the high scores when they are about to be
+    /// shown and asking for a name when the score made it onto the table.
+    fn set_state(&mut self, state: GameState) {
+        if state == self.state {
+            return;
+        }
+        self.state = state;
+        match state {
+            GameState::HighScores => self.load_high_scores(),
+            GameState::GameOver => {
+                self.load_high_scores();
+                let score = self.score();
+                if self.high_scores_path.is_some() && self.high_scores.qualifies(score) {
+                    self.name_entry = Some(String::new());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn load_high_scores(&mut self) {
+        if let Some(path) = &self.high_scores_path {
+            self.high_scores = HighScores::load(path);
+        }
+    }
+
+    /// Keeps the high scores at `path`, e.g. for a game that was loaded
+    /// with `load`.
+    pub fn set_high_scores_path(&mut self, path: &str) {
+        self.high_scores_path = Some(path.to_string());
+    }
+
+    pub fn high_scores(&self) -> &HighScores {
+        &self.high_scores
+    }
+
+    /// How long the game has been played for.
+    pub fn play_time(&self) -> Duration {
+        self.update_interval_millis.mul_f64(self.tick as f64)
+    }
+
+    /// How many players play the game.
+    pub fn n_players(&self) -> usize {
+        self.players.len()
+    }
+
+    /// Player number `number`, counting from 0.
+    pub fn player(&self, number: usize) -> &RefCell<Player> {
+        &self.players[number]
+    }
+
+    pub fn player_state(&self, number: usize) -> &RefCell<PlayerState> {
+        &self.player_states[number]
+    }
+
+    pub fn player_position(&self, number: usize) -> Point2d<f64> {
+        self.players[number].borrow().position()
+    }
+
+    pub fn player_direction(&self, number: usize) -> Point2d<f64> {
+        self.players[number].borrow().direction()
+    }
+
+    /// The numbers of the players that are still alive.
+    pub fn living_players(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.players.len()).filter(|number| self.player_states[*number].borrow().is_alive())
+    }
+
+    /// The score of all the players together.
+    pub fn score(&self) -> u32 {
+        self.player_states
+            .iter()
+            .map(|state| state.borrow().score())
+            .sum()
+    }
+
+    /// Pushes player `number` `knockback` tiles away from `from`, e.g. an
+    /// enemy that hit them, without passing through the walls.
+    pub fn knock_back_player(&self, number: usize, from: Point2d<f64>) {
+        let mut player = self.players[number].borrow_mut();
+        let mut direction = (player.position() - from).normalize();
+        if direction.x == 0.0 && direction.y == 0.0 {
+            // the enemy is right on the player, push them back where they came from
+            direction = player.direction().normalize() * -1.0;
+        }
+        let position = self.slide(player.position(), direction * self.knockback);
+        player.set_position(position);
+    }
+
+    pub fn width(&self) -> u16 {
+        self.width
+    }
+
+    pub fn height(&self) -> u16 {
+        self.height
+    }
+
+    pub fn theme(&self) -> &Theme {
+        &self.theme
+    }
+
+    /// Draws the game with `theme` from the next frame on.
+    pub fn set_theme(&mut self, theme: Theme) {
+        self.renderer.set_background(theme.background);
+        self.theme = theme;
+    }
+
+    pub fn camera(&self) -> &Camera {
+        &self.camera
+    }
+
+    /// Rows the HUD takes up below the map.
+    fn hud_height(&self) -> u16 {
+        HUD_MARGIN + self.players.len() as u16
+    }
+
+    /// Fits the map and the HUD below it into `columns` x `rows` cells. The
+    /// camera shows the part of the map around the players that fits.
+    pub fn set_screen_size(&mut self, columns: u16, rows: u16) {
+        self.screen_size = Point2d::new(columns, rows);
+        let width = columns.min(self.width).max(1);
+        let height = rows
+            .saturating_sub(self.hud_height())
+            .min(self.height)
+            .max(1);
+        self.camera.resize(width, height);
+        let positions = self.unit_positions();
+        self.camera
+            .center_on(self.players_center(&positions), self.size());
+        self.hud.borrow_mut().set_position(Point2d::new(
+            (width / 2).saturating_sub(10),
+            height + HUD_MARGIN,
+        ));
+    }
+
+    /// The smallest screen the game can be played on, with the whole map
+    /// or a large enough part of it above the HUD.
+    pub fn min_screen_size(&self) -> Point2d<u16> {
+        Point2d::new(
+            self.width.min(MIN_VIEW.x),
+            self.height.min(MIN_VIEW.y) + self.hud_height(),
+        )
+    }
+
+    /// Whether the screen is too small to play on. The game waits until it
+    /// grows again.
+    pub fn is_screen_too_small(&self) -> bool {
+        let min = self.min_screen_size();
+        self.screen_size.x < min.x || self.screen_size.y < min.y
+    }
+
+    fn size(&self) -> Point2d<u16> {
+        Point2d::new(self.width, self.height)
+    }
+
+    /// The middle of the living players, or of all of them once they died,
+    /// among the units at `positions`.
+    fn players_center(&self, positions: &[Point2d<f64>]) -> Point2d<f64> {
+        let mut players: Vec<Point2d<f64>> =
+            self.living_players().map(|number| positions[number]).collect();
+        if players.is_empty() {
+            players = positions[..self.players.len()].to_vec();
+        }
+        let sum = players
+            .iter()
+            .fold(Point2d::new(0.0, 0.0), |sum, position| sum + *position);
+        sum * (1.0 / players.len() as f64)
+    }
+
+    /// The renderer the game draws on, if it is an `R`.
+    pub fn renderer<R: Renderer>(&self) -> Option<&R> {
+        (self.renderer.as_ref() as &dyn Any).downcast_ref::<R>()
+    }
 
+    /// Takes the renderer back, e.g. to draw the next game on it without
+    /// restoring the terminal in between.
+    pub fn into_renderer(self) -> Box<dyn Renderer> {
+        self.renderer
+    }
+
+    /// The seed, length and accepted key events of the session so far.
+    pub fn replay(&self) -> Replay {
+        Replay::new(self.seed, self.tick, self.recorded_events.clone())
+    }
+
+    pub fn init(&mut self) {
         // surround the game area with walls
         for x in 0..self.width {
             self.walls.push(Wall::new(x, 0));
@@ -98,22 +382,137 @@ impl Game {
             self.walls.push(wall);
         }
 
-        // randomize enemy positions
-        self.enemies.borrow_mut().iter_mut().for_each(|enemy| {
-            self.randomize_position_f64(enemy);
-        });
+        self.spawn_players();
+        let positions = self.unit_positions();
+        self.camera
+            .center_on(self.players_center(&positions), self.size());
+
+        // place the enemies on their spawns or at random positions
+        self.enemies
+            .borrow_mut()
+            .iter_mut()
+            .enumerate()
+            .for_each(|(i, enemy)| match self.enemy_spawns.get(i) {
+                Some(spawn) => enemy.set_position(spawn.to_f64()),
+                None => {
+                    self.randomize_position_f64(enemy);
+                    while self.do_walls_collide(enemy.position().round().to_u16()) {
+                        self.randomize_position_f64(enemy);
+                    }
+                }
+            });
+
+        // place the collectibles at random positions, the first one on its
+        // spawn if there is one
+        self.collectibles
+            .borrow_mut()
+            .iter_mut()
+            .for_each(|collectible| collectible.respawn(self));
+        if let (Some(spawn), Some(collectible)) =
+            (self.collectible_spawn, self.collectibles.borrow_mut().first_mut())
+        {
+            collectible.set_position(spawn);
+        }
+    }
 
-        // randomize collectible position
-        self.collectible.borrow_mut().randomize_position(self);
+    /// Places the players after the first one on the free tiles closest to
+    /// the first one.
+    fn spawn_players(&self) {
+        let start = self.player_position(0).round().to_u16();
+        let mut taken = HashSet::from([start]);
+        let mut queue = VecDeque::from([start]);
+        let mut spawns = vec![];
+        while spawns.len() + 1 < self.players.len() {
+            let Some(tile) = queue.pop_front() else {
+                break;
+            };
+            for (dx, dy) in [(1, 0), (0, 1), (-1, 0), (0, -1)] {
+                let (Some(x), Some(y)) =
+                    (tile.x.checked_add_signed(dx), tile.y.checked_add_signed(dy))
+                else {
+                    continue;
+                };
+                let next = Point2d::new(x, y);
+                if !self.is_blocked(next) && taken.insert(next) {
+                    queue.push_back(next);
+                    spawns.push(next);
+                }
+            }
+        }
+        // without enough free tiles, the remaining players share the start
+        for (player, spawn) in self.players[1..].iter().zip(spawns) {
+            player.borrow_mut().set_position(spawn.to_f64());
+        }
     }
 
     pub fn do_walls_collide(&self, position: Point2d<u16>) -> bool {
-        self.walls.iter().any(|wall| wall.position() == position)
+        self.walls.contains(position)
+    }
+
+    /// The shortest path between two tiles that goes around the walls, see
+    /// `pathfinding::find_path`.
+    pub fn find_path(&self, from: Point2d<u16>, to: Point2d<u16>) -> Option<Vec<Point2d<u16>>> {
+        pathfinding::find_path(from, to, |position| self.is_blocked(position))
+    }
+
+    /// Where a unit at `from` ends up after moving by `movement`, stopping
+    /// in front of the walls in the way and sliding along them, see
+    /// `collision::slide`.
+    pub fn slide(&self, from: Point2d<f64>, movement: Point2d<f64>) -> Point2d<f64> {
+        collision::slide(from, movement, |position| self.is_blocked(position))
+    }
+
+    /// Whether a tile is a wall or outside of the game area.
+    fn is_blocked(&self, position: Point2d<u16>) -> bool {
+        position.x >= self.width || position.y >= self.height || self.walls.contains(position)
+    }
+
+    /// Whether the straight line between two tiles does not pass through a
+    /// wall.
+    pub fn line_of_sight(&self, from: Point2d<u16>, to: Point2d<u16>) -> bool {
+        visibility::line_of_sight(from, to, |position| self.do_walls_collide(position))
+    }
+
+    /// A random tile that is not a wall at most `radius` tiles away from
+    /// `center` in each direction, or `center` if none is found quickly.
+    pub fn random_open_tile_near(&self, center: Point2d<u16>, radius: u16) -> Point2d<u16> {
+        let mut rng = self.rng.borrow_mut();
+        let x_range = center.x.saturating_sub(radius).max(1)
+            ..=center.x.saturating_add(radius).min(self.width - 2);
+        let y_range = center.y.saturating_sub(radius).max(1)
+            ..=center.y.saturating_add(radius).min(self.height - 2);
+        if x_range.is_empty() || y_range.is_empty() {
+            return center;
+        }
+        for _ in 0..16 {
+            let tile = Point2d::new(
+                rng.gen_range(x_range.clone()),
+                rng.gen_range(y_range.clone()),
+            );
+            if !self.do_walls_collide(tile) {
+                return tile;
+            }
+        }
+        center
+    }
+
+    /// A random tile inside the surrounding walls that is not a wall.
+    pub fn random_open_tile(&self) -> Point2d<u16> {
+        let mut rng = self.rng.borrow_mut();
+        loop {
+            let tile = Point2d::new(
+                rng.gen_range(1..self.width - 1),
+                rng.gen_range(1..self.height - 1),
+            );
+            if !self.do_walls_collide(tile) {
+                return tile;
+            }
+        }
     }
 
     pub fn randomize_position_u16(&self, a_position: &mut dyn Position<u16>) {
         a_position.set_rand_position(
-            &mut self.rng.borrow_mut(),
+            &mut *self.rng.borrow_mut(),
             1..self.width - 1,
             1..self.height - 1,
         );
@@ -121,63 +520,480 @@ impl Game {
 
     pub fn randomize_position_f64(&self, a_position: &mut dyn Position<f64>) {
         a_position.set_rand_position(
-            &mut self.rng.borrow_mut(),
+            &mut *self.rng.borrow_mut(),
             1.0..(self.width - 1).into(),
             1.0..(self.height - 1).into(),
         );
     }
 
-    fn update(&mut self) {
-        self.player_movement.borrow_mut().update(self);
+    /// A kind of collectible picked at random according to the spawn
+    /// weights. Only coins spawn when every weight is zero.
+    pub fn random_collectible_kind(&self) -> CollectibleKind {
+        let weights = self.collectible_weights.iter().map(|(_, weight)| *weight);
+        match WeightedIndex::new(weights) {
+            Ok(index) => self.collectible_weights[index.sample(&mut *self.rng.borrow_mut())].0,
+            Err(_) => CollectibleKind::Coin,
+        }
+    }
+
+    /// Rebinds the keys of each player, e.g. of a game that was loaded with
+    /// `load`.
+    pub fn set_key_bindings(&mut self, bindings: Vec<Bindings>) {
+        self.bindings = bindings;
+    }
+
+    /// The player whose keys `key` is one of, and the action it stands for.
+    fn key_action(&self, key: &KeyEvent) -> Option<(usize, Action)> {
+        self.bindings
+            .iter()
+            .enumerate()
+            .find_map(|(player, bindings)| Some((player, bindings.action(key)?)))
+    }
+
+    /// Handles `key` while playing, if it is bound to an action.
+    pub fn handle_key_event(&mut self, key: KeyEvent, quit: &mut bool) {
+        if let Some((player, action)) = self.key_action(&key) {
+            self.handle_action(player, action, quit);
+        }
+    }
+
+    /// Saves the game, or records `action` of `player` for the replay and
+    /// applies it to them.
+    pub fn handle_action(&mut self, player: usize, action: Action, quit: &mut bool) {
+        if action == Action::Save {
+            let message = match self.save(&self.save_path) {
+                Ok(()) => format!("Saved to {}", self.save_path),
+                Err(error) => format!("Could not save: {}", error),
+            };
+            self.hud.borrow_mut().set_message(message);
+            return;
+        }
+
+        if player >= self.players.len() {
+            return;
+        }
+        self.recorded_events.push(ReplayEvent {
+            tick: self.tick,
+            player,
+            action,
+            released: false,
+        });
+        input::handle_action(action, &mut self.players[player].borrow_mut(), quit);
+    }
+
+    /// Records that the key of `action` of `player` was let go and applies
+    /// it to them.
+    pub fn release_action(&mut self, player: usize, action: Action) {
+        if player >= self.players.len() {
+            return;
+        }
+        self.recorded_events.push(ReplayEvent {
+            tick: self.tick,
+            player,
+            action,
+            released: true,
+        });
+        input::handle_release(action, &mut self.players[player].borrow_mut());
+    }
+
+    /// Handles a key press on the current screen. The pause, quit and
+    /// confirm keys move between the screens, the other keys are passed to
+    /// `handle_action` while playing. With the direct control scheme, the
+    /// movement keys are held until they are released.
+    pub fn handle_key(&mut self, key: KeyEvent) {
+        if self.name_entry.is_some() {
+            if key.kind != KeyEventKind::Release {
+                self.enter_name(key);
+            }
+            return;
+        }
+        let Some((player, action)) = self.key_action(&key) else {
+            return;
+        };
+        let direct = self
+            .players
+            .get(player)
+            .is_some_and(|player| player.borrow().control_scheme() == ControlScheme::Direct);
+        if direct && action.direction().is_some() {
+            self.handle_held_key(player, action, key.kind);
+            return;
+        }
+        if key.kind == KeyEventKind::Release {
+            return;
+        }
+        match (self.state, StateEvent::from_action(action)) {
+            (_, Some(event)) => self.set_state(self.state.on(event)),
+            (GameState::Playing, None) => {
+                let mut quit = false;
+                self.handle_action(player, action, &mut quit);
+            }
+            _ => {}
+        }
+    }
+
+    /// Presses the action of a held key once and releases it once, however
+    /// often the terminal repeats it.
+    fn handle_held_key(&mut self, player: usize, action: Action, kind: KeyEventKind) {
+        if self.held_keys.len() < self.players.len() {
+            self.held_keys
+                .resize_with(self.players.len(), HeldKeys::new);
+        }
+        let held_keys = &mut self.held_keys[player];
+        if kind == KeyEventKind::Release {
+            if held_keys.release(action) {
+                self.release_action(player, action);
+            }
+        } else if self.state == GameState::Playing && held_keys.press(action, Instant::now()) {
+            let mut quit = false;
+            self.handle_action(player, action, &mut quit);
+        }
+    }
+
+    /// Edits the name of a new high score. Enter saves it and escape skips
+    /// it, while Ctrl-C still quits.
+    fn enter_name(&mut self, key: KeyEvent) {
+        let Some(name) = &mut self.name_entry else {
+            return;
+        };
+        match key.code {
+            KeyCode::Char('c') if key.modifiers == KeyModifiers::CONTROL => {
+                self.name_entry = None;
+                self.set_state(self.state.on(StateEvent::Back));
+            }
+            KeyCode::Char(character)
+                if !key.modifiers.contains(KeyModifiers::CONTROL)
+                    && name.chars().count() < MAX_NAME_LENGTH =>
+            {
+                name.push(character)
+            }
+            KeyCode::Backspace => {
+                name.pop();
+            }
+            KeyCode::Enter => self.save_high_score(),
+            KeyCode::Esc => self.name_entry = None,
+            _ => {}
+        }
+    }
+
+    fn save_high_score(&mut self) {
+        let (Some(name), Some(path)) = (self.name_entry.take(), &self.high_scores_path) else {
+            return;
+        };
+        let name = match name.trim() {
+            "" => "anonymous".to_string(),
+            name => name.to_string(),
+        };
+        self.high_scores.insert(HighScore {
+            name,
+            score: self.score(),
+            duration: self.play_time(),
+            seed: self.seed,
+            map: self.map_name.clone(),
+        });
+        if let Err(error) = self.high_scores.save(path) {
+            self.hud
+                .borrow_mut()
+                .set_message(format!("Could not save the high scores: {}", error));
+        }
+    }
+
+    /// The current positions of each player and then each enemy.
+    fn unit_positions(&self) -> Vec<Point2d<f64>> {
+        self.players
+            .iter()
+            .map(|player| player.borrow().position())
+            .chain(self.enemies.borrow().iter().map(|enemy| enemy.position()))
+            .collect()
+    }
+
+    pub fn update(&mut self) {
+        self.previous_positions = self.unit_positions();
+        // the players that died stay where they are
+        let living: Vec<usize> = self.living_players().collect();
+        for number in &living {
+            let expired = self.player_states[*number].borrow_mut().update();
+            for effect in expired {
+                let message = match self.players.len() {
+                    1 => format!("{} wore off", effect.kind()),
+                    _ => format!("{} of player {} wore off", effect.kind(), number + 1),
+                };
+                self.hud.borrow_mut().set_message(message);
+            }
+        }
+        for number in living {
+            self.players[number].borrow_mut().update(self);
+        }
 
-        self.collectible.borrow_mut().update(&self);
+        self.collectibles
+            .borrow_mut()
+            .iter_mut()
+            .for_each(|collectible| collectible.update(self));
 
         self.enemies
             .borrow_mut()
             .iter_mut()
-            .for_each(|enemy: &mut Enemy| enemy.update(&self));
+            .for_each(|enemy: &mut Enemy| enemy.update(self));
 
         self.hud.borrow_mut().update(self);
+
+        self.tick += 1;
     }
 
-    fn draw(&mut self) {
-        self.ui.clear();
-        let mut buffer: Vec<u8> = Vec::new();
+    pub fn draw(&mut self) {
+        self.draw_interpolated(1.0);
+    }
+
+    /// Draws the player and the enemies `alpha` of the way from where they
+    /// were before the last update to where they are now.
+    pub fn draw_interpolated(&mut self, alpha: f64) {
+        self.renderer.clear();
+        self.draw_scene(alpha);
+        self.renderer.present();
+    }
 
-        self.walls.iter().for_each(|wall| wall.draw(&mut buffer));
-        self.player_movement.borrow().draw(&mut buffer);
+    fn draw_scene(&mut self, alpha: f64) {
+        let current_positions = self.unit_positions();
+        let positions: Vec<Point2d<f64>> = current_positions
+            .iter()
+            .enumerate()
+            .map(|(i, current)| match self.previous_positions.get(i) {
+                Some(previous) => timestep::interpolate(*previous, *current, alpha),
+                None => *current,
+            })
+            .collect();
+
+        let center = self.players_center(&positions);
+        self.camera.follow(center, self.size());
+
+        let (player_positions, enemy_positions) = positions.split_at(self.players.len());
+        let (theme, camera) = (&self.theme, &self.camera);
+        let renderer = self.renderer.as_mut();
+        // only the walls in view are looked at, the map can be huge
+        camera
+            .visible_tiles()
+            .filter(|tile| self.walls.contains(*tile))
+            .for_each(|tile| Wall::new(tile.x, tile.y).draw(theme, camera, renderer));
+        for ((player, state), position) in self
+            .players
+            .iter()
+            .zip(&self.player_states)
+            .zip(player_positions)
+        {
+            if state.borrow().is_alive() {
+                player
+                    .borrow()
+                    .draw_at(*position, theme, camera, renderer);
+            }
+        }
         self.enemies
             .borrow()
             .iter()
-            .for_each(|enemy| enemy.draw(&mut buffer));
-        self.collectible.borrow().draw(&mut buffer);
-        self.hud.borrow().draw(&mut buffer);
+            .zip(enemy_positions)
+            .for_each(|(enemy, position)| enemy.draw_at(*position, theme, camera, renderer));
+        self.collectibles
+            .borrow()
+            .iter()
+            .for_each(|collectible| collectible.draw(theme, camera, renderer));
+        self.hud.borrow().draw(theme, camera, renderer);
+    }
+
+    /// Draws the screen of the current state, on top of the game unless it
+    /// is the title screen.
+    pub fn render(&mut self, alpha: f64) {
+        if self.is_screen_too_small() {
+            let screen = Screen::TooSmall {
+                size: self.screen_size,
+                min_size: self.min_screen_size(),
+            };
+            self.renderer.clear();
+            screen.draw(
+                self.renderer.as_mut(),
+                self.screen_size.x,
+                self.screen_size.y,
+            );
+            self.renderer.present();
+            return;
+        }
 
-        self.stdout
-            .write_all(&buffer)
-            .expect("failed to write to stdout");
-        self.stdout.flush().expect("Failed to flush stdout");
+        let screen = match self.state {
+            GameState::Menu => Some(Screen::Title),
+            GameState::HighScores => Some(Screen::HighScores {
+                entries: self.high_scores.entries().to_vec(),
+            }),
+            GameState::Paused => Some(Screen::Paused),
+            GameState::GameOver => Some(Screen::GameOver {
+                score: self.score(),
+                name_entry: self.name_entry.clone(),
+            }),
+            GameState::Playing | GameState::Finished(_) => None,
+        };
+
+        self.renderer.clear();
+        if !matches!(self.state, GameState::Menu | GameState::HighScores) {
+            self.draw_scene(alpha);
+        }
+        if let Some(screen) = screen {
+            screen.draw(
+                self.renderer.as_mut(),
+                self.camera.width(),
+                self.camera.height(),
+            );
+        }
+        self.renderer.present();
+    }
+
+    /// Shows the title screen and then plays the game. The terminal stays
+    /// prepared until the renderer is dropped, so that a restarted game can
+    /// take it over with `into_renderer`.
+    pub fn run(&mut self) -> RunOutcome {
+        self.start(GameState::Menu)
+    }
+
+    /// Like `run`, but starts playing right away, e.g. after a restart.
+    pub fn play(&mut self) -> RunOutcome {
+        self.start(GameState::Playing)
     }
 
-    pub fn run(&mut self) {
+    fn start(&mut self, state: GameState) -> RunOutcome {
+        self.renderer.prepare();
         self.init();
-        let mut quit = false;
-        while self.player_state.borrow().is_alive() && !quit {
-            // poll for key events for the duration of the update interval
-            let now = std::time::Instant::now();
-            while let Some(time_remaining) = self.update_interval_millis.checked_sub(now.elapsed())
-            {
-                if let Some(key) = input::poll_key_event(time_remaining) {
-                    input::handle_key_event(key, &mut self.player_movement.borrow_mut(), &mut quit);
+        self.state = state;
+        self.game_loop(None)
+    }
+
+    /// Continues a game that was already initialized, e.g. one that was
+    /// loaded with `load`. The game starts paused.
+    pub fn resume(&mut self) -> RunOutcome {
+        self.renderer.prepare();
+        self.state = GameState::Paused;
+        self.game_loop(None)
+    }
+
+    /// Ends the game once every player died, or once `replay` ran out or its
+    /// players died.
+    fn check_game_over(&mut self, replay: Option<&Replay>) {
+        let alive = self.living_players().next().is_some();
+        match replay {
+            Some(replay) if !alive || self.tick >= replay.ticks() => {
+                self.state = GameState::Finished(RunOutcome::Quit)
+            }
+            None if !alive => self.set_state(self.state.on(StateEvent::PlayerDied)),
+            _ => {}
+        }
+    }
+
+    /// Updates the game every `update_interval_millis` while playing and
+    /// draws it every `frame_interval`, interpolating the units in between.
+    /// The keys are handled as soon as they are pressed. With a `replay` the
+    /// player is driven by it, and the keyboard can only pause and quit.
+    /// The layout follows the size of the terminal, and nothing moves while
+    /// it is too small. SIGINT and SIGTERM end the run.
+    fn game_loop(&mut self, replay: Option<&Replay>) -> RunOutcome {
+        let mut timestep = FixedTimestep::new(*self.update_interval_millis);
+        let mut last_time = Instant::now();
+        let mut next_frame = last_time;
+
+        loop {
+            if terminal::is_interrupted() {
+                self.state = GameState::Finished(RunOutcome::Quit);
+            }
+            if let GameState::Finished(outcome) = self.state {
+                return outcome;
+            }
+
+            while let Some(event) = input::poll_event(Duration::ZERO) {
+                let key = match event {
+                    Event::Key(key) => key,
+                    Event::Resize(columns, rows) => {
+                        self.renderer.resize(columns, rows);
+                        self.set_screen_size(columns, rows);
+                        continue;
+                    }
+                    _ => continue,
+                };
+                // on a screen that is too small, only quitting is possible
+                let too_small = self.is_screen_too_small();
+                match (replay, self.key_action(&key).map(|(_, action)| action)) {
+                    (None, _) if !too_small || key.kind == KeyEventKind::Release => {
+                        self.handle_key(key)
+                    }
+                    _ if key.kind == KeyEventKind::Release => {}
+                    (_, Some(Action::Quit)) => self.state = GameState::Finished(RunOutcome::Quit),
+                    (Some(_), Some(Action::Pause)) => {
+                        self.set_state(self.state.on(StateEvent::TogglePause))
+                    }
+                    _ => {}
                 }
             }
 
-            self.update();
-            self.draw();
+            // the time spent on the other screens is not simulated
+            let now = Instant::now();
+            if replay.is_none() {
+                for player in 0..self.held_keys.len() {
+                    for action in self.held_keys[player].expire(now) {
+                        self.release_action(player, action);
+                    }
+                }
+            }
+            let elapsed = now - last_time;
+            last_time = now;
+            if self.state == GameState::Playing && !self.is_screen_too_small() {
+                for _ in 0..timestep.advance(elapsed) {
+                    match replay {
+                        Some(replay) => self.replay_tick(replay),
+                        None => self.update(),
+                    }
+                    self.check_game_over(replay);
+                    if self.state != GameState::Playing {
+                        break;
+                    }
+                }
+            }
+
+            if now >= next_frame {
+                self.render(timestep.alpha());
+                next_frame = now + self.frame_interval;
+            }
+
+            // sleep until the next update or frame is due, or a key is pressed
+            let mut timeout = next_frame.saturating_duration_since(Instant::now());
+            if self.state == GameState::Playing && !self.is_screen_too_small() {
+                timeout = timeout.min(timestep.until_next_step());
+            }
+            input::wait_for_event(timeout);
         }
-        self.ui.restore();
-        print!("\nGame over!");
-        println!("  Score: {}", self.player_state().borrow().score());
+    }
+
+    fn replay_tick(&mut self, replay: &Replay) {
+        // quitting is decided by the number of recorded ticks instead
+        let mut quit = false;
+        for event in replay.events_at(self.tick) {
+            if event.released {
+                self.release_action(event.player, event.action);
+            } else {
+                self.handle_action(event.player, event.action, &mut quit);
+            }
+        }
+        self.update();
+    }
+
+    /// Feeds `replay` into a freshly built game as fast as possible and
+    /// without drawing. The game must be built with the same options as the
+    /// recorded one and the seed of `replay`, which the map is laid out with.
+    pub fn play_back(&mut self, replay: &Replay) {
+        self.init();
+        while self.tick < replay.ticks() {
+            self.replay_tick(replay);
+        }
+    }
+
+    /// Like `play`, but the player is driven by `replay` instead of the
+    /// keyboard. The quit keys still stop the playback.
+    pub fn run_replay(&mut self, replay: &Replay) {
+        self.renderer.prepare();
+        self.init();
+        self.state = GameState::Playing;
+        self.game_loop(Some(replay));
     }
 }
 
@@ -1,26 +1,48 @@
-use std::{io::stdout, time::Duration};
+use std::time::Duration;
 
-use rand::RngCore;
+use rand::SeedableRng;
+use rand_chacha::ChaCha12Rng;
 
 use crate::{
     hud::Hud,
+    input::{Bindings, ControlScheme, HeldKeys},
+    level::Level,
+    map::MapGenerator,
     point::Point2d,
-    ui::UI,
-    unit::{Collectible, Enemy, PlayerBuilder, PlayerState, Wall},
+    scores::HighScores,
+    ui::{Camera, Renderer, Theme, UI},
+    unit::{Collectible, CollectibleKind, Enemy, PlayerBuilder, PlayerState, Wall, Walls},
 };
 
-use super::Game;
+use super::{Game, GameState};
 
 pub struct GameBuilder {
     height: u16,
     width: u16,
     n_random_walls: u16,
     update_interval: Duration,
+    frame_interval: Duration,
     player_builder: PlayerBuilder,
+    n_players: usize,
     player_health: u8,
+    player_invulnerability: u32,
+    knockback: f64,
     enemies: Vec<Enemy>,
+    enemy_spawns: Vec<Point2d<u16>>,
+    collectible_spawn: Option<Point2d<u16>>,
+    n_collectibles: u16,
+    collectible_weights: Vec<(CollectibleKind, u32)>,
     walls: Vec<Wall>,
-    rng: Box<dyn RngCore>,
+    map_generator: Option<Box<dyn MapGenerator>>,
+    seed: u64,
+    save_path: String,
+    map_name: String,
+    high_scores_path: Option<String>,
+    bindings: Option<Vec<Bindings>>,
+    screen_size: Option<(u16, u16)>,
+    camera: Camera,
+    theme: Theme,
+    renderer: Box<dyn Renderer>,
 }
 
 impl GameBuilder {
@@ -30,16 +52,33 @@ impl GameBuilder {
             height: 48,
             width: 80,
             player_builder: PlayerBuilder::new(),
+            n_players: 1,
             player_health: 10,
+            player_invulnerability: 10,
+            knockback: 2.0,
             n_random_walls: 0,
             update_interval: Duration::from_millis(50),
+            frame_interval: Duration::from_secs(1) / 30,
             enemies: vec![
                 Enemy::with_speed(0.6),
                 Enemy::with_speed(0.5),
                 Enemy::with_speed(0.4),
             ],
+            enemy_spawns: vec![],
+            collectible_spawn: None,
+            n_collectibles: 3,
+            collectible_weights: CollectibleKind::default_weights(),
             walls: vec![],
-            rng: Box::new(rand::thread_rng()),
+            map_generator: None,
+            seed: rand::random(),
+            save_path: "savegame.json".to_string(),
+            map_name: "random".to_string(),
+            high_scores_path: None,
+            bindings: None,
+            screen_size: None,
+            camera: Camera::default(),
+            theme: Theme::default(),
+            renderer: Box::new(UI::new()),
         }
     }
 
@@ -58,11 +97,41 @@ impl GameBuilder {
         self
     }
 
+    /// How many updates the player can not be hurt after an enemy hit.
+    pub fn player_invulnerability(mut self, ticks: u32) -> Self {
+        self.player_invulnerability = ticks;
+        self
+    }
+
+    /// How many tiles an enemy hit pushes the player away.
+    pub fn knockback(mut self, knockback: f64) -> Self {
+        self.knockback = knockback;
+        self
+    }
+
     pub fn player_starting_speed(mut self, speed: f64) -> Self {
         self.player_builder = self.player_builder.speed(speed);
         self
     }
 
+    /// How many players play on the same keyboard. The players after the
+    /// first one start next to it.
+    pub fn n_players(mut self, n_players: usize) -> Self {
+        self.n_players = n_players.max(1);
+        self
+    }
+
+    /// How the movement keys steer the players.
+    pub fn control_scheme(mut self, control: ControlScheme) -> Self {
+        self.player_builder = self.player_builder.control_scheme(control);
+        self
+    }
+
+    pub fn player_starting_position(mut self, x: f64, y: f64) -> Self {
+        self.player_builder = self.player_builder.position(x, y);
+        self
+    }
+
     pub fn n_random_walls(mut self, n_random_walls: u16) -> Self {
         self.n_random_walls = n_random_walls;
         self
@@ -73,36 +142,212 @@ impl GameBuilder {
         self
     }
 
+    /// Time between two frames. The units are drawn in between two updates
+    /// when it is shorter than the update interval.
+    pub fn frame_interval(mut self, frame_interval: Duration) -> Self {
+        self.frame_interval = frame_interval;
+        self
+    }
+
     pub fn enemies(mut self, enemies: Vec<Enemy>) -> Self {
         self.enemies = enemies;
         self
     }
 
+    /// Places one enemy on each spawn instead of at random positions. The
+    /// speeds are taken from `enemies` in turn.
+    pub fn enemy_spawns(mut self, enemy_spawns: Vec<Point2d<u16>>) -> Self {
+        self.enemy_spawns = enemy_spawns;
+        self
+    }
+
+    /// Where the first collectible appears, instead of a random position.
+    pub fn collectible_spawn(mut self, position: Point2d<u16>) -> Self {
+        self.collectible_spawn = Some(position);
+        self
+    }
+
+    /// How many collectibles are in the game at the same time.
+    pub fn n_collectibles(mut self, n_collectibles: u16) -> Self {
+        self.n_collectibles = n_collectibles;
+        self
+    }
+
+    /// How often each kind of collectible spawns relative to the others.
+    /// Kinds that are left out never spawn.
+    pub fn collectible_weights(mut self, collectible_weights: Vec<(CollectibleKind, u32)>) -> Self {
+        self.collectible_weights = collectible_weights;
+        self
+    }
+
     pub fn walls(mut self, walls: Vec<Wall>) -> Self {
         self.walls = walls;
         self
     }
 
-    pub fn rng(mut self, rng: Box<dyn RngCore>) -> Self {
-        self.rng = rng;
+    /// Lays the walls out with `map_generator` when the game is built. The
+    /// generator is seeded from the game's random number generator. No
+    /// random walls are added then, as they could cut the map apart.
+    pub fn map_generator(mut self, map_generator: Box<dyn MapGenerator>) -> Self {
+        self.map_generator = Some(map_generator);
+        self
+    }
+
+    /// Takes the size, walls, spawns and player start from `level`.
+    pub fn level(self, level: &Level) -> Self {
+        let player_start = level.player_start().to_f64();
+        let builder = self
+            .width(level.width())
+            .height(level.height())
+            .n_random_walls(0)
+            .walls(level.walls())
+            .player_starting_position(player_start.x, player_start.y)
+            .enemy_spawns(level.enemy_spawns().to_vec());
+
+        match level.collectible() {
+            Some(position) => builder.collectible_spawn(position),
+            None => builder,
+        }
+    }
+
+    /// Seeds the random number generator, making the game reproducible.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Where the game is saved when the player presses the save key.
+    pub fn save_path(mut self, save_path: &str) -> Self {
+        self.save_path = save_path.to_string();
+        self
+    }
+
+    /// The name the high scores of the game are kept with, e.g. the kind
+    /// of generated map or the path of the level.
+    pub fn map_name(mut self, map_name: &str) -> Self {
+        self.map_name = map_name.to_string();
+        self
+    }
+
+    /// Where the high scores are kept. Without it, no high scores are
+    /// entered.
+    pub fn high_scores_path(mut self, high_scores_path: &str) -> Self {
+        self.high_scores_path = Some(high_scores_path.to_string());
+        self
+    }
+
+    /// Which keys stand for which actions, for each player. By default the
+    /// players share the keyboard as in `Bindings::defaults`.
+    pub fn key_bindings(mut self, bindings: Vec<Bindings>) -> Self {
+        self.bindings = Some(bindings);
+        self
+    }
+
+    /// How many columns and rows the game is drawn in, together with the
+    /// HUD. A map that does not fit is shown around the players. By default
+    /// the whole map is shown.
+    pub fn screen_size(mut self, columns: u16, rows: u16) -> Self {
+        self.screen_size = Some((columns, rows));
+        self
+    }
+
+    /// How far the players can move in the middle of the screen before the
+    /// map scrolls along.
+    pub fn camera_dead_zone(mut self, width: u16, height: u16) -> Self {
+        self.camera = self.camera.with_dead_zone(width, height);
+        self
+    }
+
+    /// Whether the map stops scrolling at its edges, instead of keeping the
+    /// players near the middle of the screen.
+    pub fn camera_clamping(mut self, clamped: bool) -> Self {
+        self.camera = self.camera.with_clamping(clamped);
+        self
+    }
+
+    /// The glyphs and colours the game is drawn with.
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    pub fn renderer(mut self, renderer: Box<dyn Renderer>) -> Self {
+        self.renderer = renderer;
         self
     }
 
     pub fn build(self) -> Game {
-        Game {
+        let enemies = if self.enemy_spawns.is_empty() {
+            self.enemies
+        } else {
+            self.enemies
+                .iter()
+                .cycle()
+                .take(self.enemy_spawns.len())
+                .cloned()
+                .collect()
+        };
+
+        let mut rng = ChaCha12Rng::seed_from_u64(self.seed);
+        let mut walls = Walls::from(self.walls);
+        if let Some(map_generator) = &self.map_generator {
+            let start = self.player_builder.starting_position().round().to_u16();
+            let map = map_generator.generate_connected(self.width, self.height, start, &mut rng);
+            walls.extend(map.inner_walls());
+        }
+
+        let mut game = Game {
             height: self.height,
             width: self.width,
-            n_random_walls: self.n_random_walls,
+            n_random_walls: match self.map_generator {
+                Some(_) => 0,
+                None => self.n_random_walls,
+            },
             update_interval_millis: Box::new(self.update_interval),
-            enemies: self.enemies.into(),
-            walls: self.walls,
-            collectible: Collectible::default().into(),
-            player_movement: self.player_builder.build().into(),
-            player_state: PlayerState::new(self.player_health, 0).into(),
-            ui: UI::new(),
-            rng: self.rng.into(),
-            stdout: stdout(),
-            hud: Hud::new(Point2d::new(self.width / 2 - 10, self.height + 2)).into(),
-        }
+            frame_interval: self.frame_interval,
+            previous_positions: vec![],
+            state: GameState::default(),
+            camera: self.camera,
+            screen_size: Point2d::new(0, 0),
+            theme: Theme::default(),
+            bindings: self
+                .bindings
+                .unwrap_or_else(|| Bindings::defaults(self.n_players)),
+            held_keys: vec![HeldKeys::new(); self.n_players],
+            map_name: self.map_name,
+            high_scores_path: self.high_scores_path,
+            high_scores: HighScores::default(),
+            name_entry: None,
+            enemies: enemies.into(),
+            enemy_spawns: self.enemy_spawns,
+            collectible_spawn: self.collectible_spawn,
+            walls,
+            collectibles: vec![Collectible::default(); self.n_collectibles.into()].into(),
+            collectible_weights: self.collectible_weights,
+            players: (0..self.n_players)
+                .map(|number| self.player_builder.clone().number(number).build().into())
+                .collect(),
+            player_states: (0..self.n_players)
+                .map(|_| {
+                    PlayerState::new(self.player_health, 0)
+                        .with_invulnerability(self.player_invulnerability)
+                        .into()
+                })
+                .collect(),
+            knockback: self.knockback,
+            renderer: self.renderer,
+            rng: rng.into(),
+            seed: self.seed,
+            tick: 0,
+            recorded_events: vec![],
+            save_path: self.save_path,
+            hud: Hud::new(Point2d::new(0, 0)).into(),
+        };
+        let (columns, rows) = self
+            .screen_size
+            .unwrap_or((game.width, game.height + game.hud_height()));
+        game.set_screen_size(columns, rows);
+        game.set_theme(self.theme);
+        game
     }
 }
@@ -0,0 +1,62 @@
+use std::error::Error;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{common::JsonIo, input::Action};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReplayEvent {
+    /// Number of updates that happened before the event was handled.
+    pub tick: u64,
+    /// The player the action is of, counting from 0.
+    #[serde(default)]
+    pub player: usize,
+    pub action: Action,
+    /// Whether the key of the action was let go instead of pressed.
+    #[serde(default)]
+    pub released: bool,
+}
+
+/// Everything needed to reproduce a session: the seed the game was built
+/// with, the number of updates it ran for and every action it accepted.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Replay {
+    seed: u64,
+    ticks: u64,
+    events: Vec<ReplayEvent>,
+}
+
+impl Replay {
+    pub fn new(seed: u64, ticks: u64, events: Vec<ReplayEvent>) -> Self {
+        Self {
+            seed,
+            ticks,
+            events,
+        }
+    }
+
+    pub fn load(path: &str) -> Result<Self, Box<dyn Error>> {
+        JsonIo::new(path).read_json()
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), Box<dyn Error>> {
+        JsonIo::new(path).write_json(self)
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn ticks(&self) -> u64 {
+        self.ticks
+    }
+
+    pub fn events(&self) -> &[ReplayEvent] {
+        &self.events
+    }
+
+    /// The events that were handled right before update number `tick`.
+    pub fn events_at(&self, tick: u64) -> impl Iterator<Item = &ReplayEvent> + '_ {
+        self.events.iter().filter(move |event| event.tick == tick)
+    }
+}
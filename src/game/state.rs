@@ -0,0 +1,81 @@
+mod tests;
+
+use crate::input::Action;
+
+/// How a run of the game ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RunOutcome {
+    /// The player wants to play again with a new game.
+    Restart,
+    Quit,
+}
+
+/// Which screen the game is on.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum GameState {
+    /// The title screen, shown before the game starts.
+    #[default]
+    Menu,
+    /// The high score table, opened from the title screen.
+    HighScores,
+    Playing,
+    Paused,
+    /// The player died or gave up, the score is shown.
+    GameOver,
+    /// The run is over and the game loop stops.
+    Finished(RunOutcome),
+}
+
+/// Something that moves the game from one state to another.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StateEvent {
+    /// The confirm keys, which start, resumes or restarts the game.
+    Confirm,
+    /// The pause key.
+    TogglePause,
+    /// The quit keys, which go back one step.
+    Back,
+    /// The high score key.
+    ShowHighScores,
+    PlayerDied,
+}
+
+impl StateEvent {
+    /// The event an action stands for, if any.
+    pub fn from_action(action: Action) -> Option<Self> {
+        match action {
+            Action::Quit => Some(StateEvent::Back),
+            Action::Pause => Some(StateEvent::TogglePause),
+            Action::HighScores => Some(StateEvent::ShowHighScores),
+            Action::Confirm => Some(StateEvent::Confirm),
+            _ => None,
+        }
+    }
+}
+
+impl GameState {
+    /// The state after `event`. Events that mean nothing in a state leave
+    /// it unchanged.
+    pub fn on(self, event: StateEvent) -> Self {
+        use GameState::*;
+        use StateEvent::*;
+
+        match (self, event) {
+            (Menu, Confirm) => Playing,
+            (Menu, Back) => Finished(RunOutcome::Quit),
+            (Menu, ShowHighScores) => HighScores,
+            (HighScores, Confirm) | (HighScores, Back) | (HighScores, ShowHighScores) => Menu,
+            (Playing, TogglePause) => Paused,
+            (Playing, Back) | (Playing, PlayerDied) => GameOver,
+            (Paused, TogglePause) | (Paused, Confirm) => Playing,
+            (Paused, Back) => GameOver,
+            (GameOver, Confirm) => Finished(RunOutcome::Restart),
+            (GameOver, Back) => Finished(RunOutcome::Quit),
+            (state, _) => state,
+        }
+    }
+
+    pub fn is_finished(self) -> bool {
+        matches!(self, GameState::Finished(_))
+    }
+}
@@ -0,0 +1,97 @@
+#![cfg(test)]
+
+use super::*;
+
+#[test]
+fn test_a_whole_run() {
+    let events = [
+        StateEvent::Confirm,
+        StateEvent::TogglePause,
+        StateEvent::TogglePause,
+        StateEvent::PlayerDied,
+        StateEvent::Confirm,
+    ];
+    let states: Vec<GameState> = events
+        .iter()
+        .scan(GameState::default(), |state, event| {
+            *state = state.on(*event);
+            Some(*state)
+        })
+        .collect();
+
+    assert_eq!(
+        states,
+        vec![
+            GameState::Playing,
+            GameState::Paused,
+            GameState::Playing,
+            GameState::GameOver,
+            GameState::Finished(RunOutcome::Restart),
+        ]
+    );
+}
+
+#[test]
+fn test_back_goes_one_step_back() {
+    assert_eq!(
+        GameState::Menu.on(StateEvent::Back),
+        GameState::Finished(RunOutcome::Quit)
+    );
+    assert_eq!(GameState::Playing.on(StateEvent::Back), GameState::GameOver);
+    assert_eq!(GameState::Paused.on(StateEvent::Back), GameState::GameOver);
+    assert_eq!(
+        GameState::GameOver.on(StateEvent::Back),
+        GameState::Finished(RunOutcome::Quit)
+    );
+}
+
+#[test]
+fn test_high_scores_are_viewed_from_the_menu() {
+    let state = GameState::Menu.on(StateEvent::ShowHighScores);
+    assert_eq!(state, GameState::HighScores);
+    assert_eq!(state.on(StateEvent::Confirm), GameState::Menu);
+    assert_eq!(state.on(StateEvent::Back), GameState::Menu);
+    assert_eq!(state.on(StateEvent::TogglePause), GameState::HighScores);
+    assert_eq!(
+        GameState::Playing.on(StateEvent::ShowHighScores),
+        GameState::Playing
+    );
+}
+
+#[test]
+fn test_meaningless_events_are_ignored() {
+    assert_eq!(GameState::Menu.on(StateEvent::TogglePause), GameState::Menu);
+    assert_eq!(
+        GameState::Playing.on(StateEvent::Confirm),
+        GameState::Playing
+    );
+    assert_eq!(
+        GameState::Paused.on(StateEvent::PlayerDied),
+        GameState::Paused
+    );
+    assert_eq!(
+        GameState::GameOver.on(StateEvent::TogglePause),
+        GameState::GameOver
+    );
+    let finished = GameState::Finished(RunOutcome::Quit);
+    assert_eq!(finished.on(StateEvent::Confirm), finished);
+    assert!(finished.is_finished());
+}
+
+#[test]
+fn test_actions_map_to_events() {
+    assert_eq!(
+        StateEvent::from_action(Action::Confirm),
+        Some(StateEvent::Confirm)
+    );
+    assert_eq!(
+        StateEvent::from_action(Action::Pause),
+        Some(StateEvent::TogglePause)
+    );
+    assert_eq!(StateEvent::from_action(Action::Quit), Some(StateEvent::Back));
+    assert_eq!(
+        StateEvent::from_action(Action::HighScores),
+        Some(StateEvent::ShowHighScores)
+    );
+    assert_eq!(StateEvent::from_action(Action::TurnLeft), None);
+}
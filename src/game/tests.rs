@@ -2,16 +2,17 @@
 
 use std::error::Error;
 
-use rand::{rngs::StdRng, SeedableRng};
+use crossterm::{
+    event::{KeyCode, KeyModifiers},
+    style::Color,
+};
 
-use crate::common::JsonIo;
+use crate::{common::JsonIo, ui::GridRenderer};
 
 use super::*;
 
 #[test]
 fn test_game_after_updates() -> Result<(), Box<dyn Error>> {
-    let rng = StdRng::seed_from_u64(42);
-
     let mut game = Game::builder()
         .n_random_walls(30)
         .height(40)
@@ -23,9 +24,10 @@ fn test_game_after_updates() -> Result<(), Box<dyn Error>> {
                 .collect(),
         )
         .update_interval(std::time::Duration::from_millis(280))
-        .rng(Box::new(rng))
+        .seed(42)
+        .renderer(Box::new(GridRenderer::new(80, 43)))
         .build();
-    
+
     let json_io = JsonIo::new("src/game/initial_game.json");
     // json_io.write_json(&game)?;
     assert_eq!(game, json_io.read_json()?);
@@ -41,4 +43,575 @@ fn test_game_after_updates() -> Result<(), Box<dyn Error>> {
     assert_eq!(game, read_game);
 
     Ok(())
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_game_draws_to_grid_renderer() {
+    let mut game = Game::builder()
+        .width(20)
+        .height(10)
+        .enemies(vec![])
+        .seed(42)
+        .renderer(Box::new(GridRenderer::new(20, 13)))
+        .build();
+
+    game.init();
+    game.update();
+    game.draw();
+
+    let grid = game.renderer::<GridRenderer>().unwrap();
+    assert_eq!(grid.frames(), 1);
+    assert_eq!(grid.row(0), "▓".repeat(20));
+    assert_eq!(grid.row(9), "▓".repeat(20));
+    assert_eq!(grid.glyph(Point2d::new(1, 1)), Some('•'));
+    assert_eq!(grid.row(12), "Health: 10, Score: 0");
+}
+
+#[test]
+fn test_game_is_drawn_with_its_theme() {
+    let theme = Theme::parse(r#"{"base": "ascii", "background": "dark_blue"}"#).unwrap();
+    let mut game = Game::builder()
+        .width(20)
+        .height(10)
+        .enemies(vec![])
+        .player_starting_position(3.0, 3.0)
+        .theme(theme)
+        .seed(42)
+        .renderer(Box::new(GridRenderer::new(20, 13)))
+        .build();
+    game.init();
+    game.draw();
+
+    let grid = game.renderer::<GridRenderer>().unwrap();
+    assert_eq!(grid.background(), Color::DarkBlue);
+    assert_eq!(grid.row(0), "#".repeat(20));
+    assert_eq!(grid.glyph(Point2d::new(3, 3)), Some('@'));
+}
+
+#[test]
+fn test_camera_follows_the_player_on_a_large_map() {
+    let mut game = Game::builder()
+        .width(500)
+        .height(500)
+        .enemies(vec![])
+        .player_starting_position(250.0, 250.0)
+        .player_starting_speed(0.0)
+        .screen_size(40, 23)
+        .seed(42)
+        .renderer(Box::new(GridRenderer::new(40, 23)))
+        .build();
+    game.init();
+    game.update();
+    game.draw();
+
+    let icon = Some(game.theme().player.glyphs[0]);
+    let grid = game.renderer::<GridRenderer>().unwrap();
+    assert_eq!((game.camera().width(), game.camera().height()), (40, 20));
+    assert_eq!(grid.glyph(Point2d::new(20, 10)), icon);
+    assert!(grid.row(22).contains("Health: 10"));
+    // the walls around the map are out of view
+    assert_eq!(grid.row(0).trim(), "");
+
+    // the camera stays put while the player is in the dead zone
+    game.player(0)
+        .borrow_mut()
+        .set_position(Point2d::new(257.0, 250.0));
+    game.draw();
+    assert_eq!(game.camera().origin(), Point2d::new(230, 240));
+
+    game.player(0)
+        .borrow_mut()
+        .set_position(Point2d::new(262.0, 250.0));
+    game.draw();
+    assert_eq!(game.camera().origin(), Point2d::new(235, 240));
+    let grid = game.renderer::<GridRenderer>().unwrap();
+    assert_eq!(grid.glyph(Point2d::new(27, 10)), icon);
+
+    // near the corner, the camera stops at the walls
+    game.player(0).borrow_mut().set_position(Point2d::new(3.0, 3.0));
+    game.draw();
+    assert_eq!(game.camera().origin(), Point2d::new(0, 0));
+    let grid = game.renderer::<GridRenderer>().unwrap();
+    assert_eq!(grid.row(0), "▓".repeat(40));
+}
+
+#[test]
+fn test_game_waits_for_the_screen_to_grow() {
+    let mut game = Game::builder()
+        .width(30)
+        .height(20)
+        .enemies(vec![])
+        .seed(42)
+        .renderer(Box::new(GridRenderer::new(60, 23)))
+        .build();
+    game.init();
+    game.update();
+    assert_eq!(game.min_screen_size(), Point2d::new(30, 23));
+    assert!(!game.is_screen_too_small());
+
+    game.set_screen_size(60, 22);
+    assert!(game.is_screen_too_small());
+    game.render(1.0);
+    let grid = game.renderer::<GridRenderer>().unwrap();
+    assert!(grid.rows().iter().any(|row| row.contains("TERMINAL TOO SMALL")));
+    assert!(grid.rows().iter().any(|row| row.contains("Needs 30x23, is 60x22")));
+
+    // the map and the HUD are laid out again once it fits
+    game.set_screen_size(60, 23);
+    game.state = GameState::Playing;
+    game.render(1.0);
+    let grid = game.renderer::<GridRenderer>().unwrap();
+    assert_eq!(grid.row(0), "▓".repeat(30) + &" ".repeat(30));
+    assert!(grid.row(22).contains("Health: 10"));
+
+    // a map larger than the screen only needs part of it to fit
+    game.width = 500;
+    assert_eq!(game.min_screen_size(), Point2d::new(40, 23));
+}
+
+#[test]
+fn test_units_are_drawn_in_between_updates() {
+    let mut game = Game::builder()
+        .width(20)
+        .height(10)
+        .player_starting_position(2.0, 4.0)
+        .player_starting_speed(4.0)
+        .update_interval(std::time::Duration::from_secs(1))
+        .enemies(vec![])
+        .seed(42)
+        .renderer(Box::new(GridRenderer::new(20, 13)))
+        .build();
+    game.init();
+    game.update();
+    assert_eq!(game.player_position(0).round().to_u16(), Point2d::new(6, 4));
+
+    let player_at = |game: &Game| -> Vec<u16> {
+        (0..20)
+            .filter(|x| {
+                game.renderer::<GridRenderer>().unwrap().glyph(Point2d::new(*x, 4)) == Some('→')
+            })
+            .collect()
+    };
+    game.draw_interpolated(0.0);
+    assert_eq!(player_at(&game), vec![2]);
+    game.draw_interpolated(0.5);
+    assert_eq!(player_at(&game), vec![4]);
+    game.draw_interpolated(1.0);
+    assert_eq!(player_at(&game), vec![6]);
+}
+
+#[test]
+fn test_collectibles_spawn_by_weight_and_apply_their_effects() {
+    let mut game = Game::builder()
+        .width(20)
+        .height(10)
+        .player_starting_speed(0.0)
+        .enemies(vec![])
+        .n_collectibles(5)
+        .collectible_weights(vec![(CollectibleKind::Shield, 1), (CollectibleKind::Coin, 0)])
+        .seed(42)
+        .renderer(Box::new(GridRenderer::new(20, 13)))
+        .build();
+    game.init();
+    assert!(game
+        .collectibles
+        .borrow()
+        .iter()
+        .all(|collectible| collectible.kind() == CollectibleKind::Shield));
+
+    let player = game.player_position(0).round().to_u16();
+    game.collectibles.borrow_mut()[0] = Collectible::new(player, CollectibleKind::Shield);
+    game.update();
+    assert!(game.player_state(0).borrow().is_shielded());
+    assert_ne!(game.collectibles.borrow()[0].position(), player);
+
+    for _ in 0..CollectibleKind::Shield.duration() {
+        game.update();
+    }
+    assert!(!game.player_state(0).borrow().is_shielded());
+
+    game.player_state(0).borrow_mut().decrease_health();
+    game.collectibles.borrow_mut()[0] = Collectible::new(player, CollectibleKind::Heart);
+    game.update();
+    assert_eq!(game.player_state(0).borrow().health(), 10);
+    game.collectibles.borrow_mut()[0] = Collectible::new(player, CollectibleKind::Heart);
+    game.update();
+    assert_eq!(game.player_state(0).borrow().health(), 10);
+
+    game.collectibles.borrow_mut()[0] = Collectible::new(player, CollectibleKind::Coin);
+    game.update();
+    assert_eq!(game.player_state(0).borrow().score(), 1);
+}
+
+#[test]
+fn test_enemy_hits_are_followed_by_invulnerability_and_knockback() {
+    let mut game = Game::builder()
+        .width(20)
+        .height(10)
+        .player_starting_position(3.4, 4.0)
+        .player_starting_speed(0.0)
+        .player_invulnerability(5)
+        .knockback(3.0)
+        .enemies(vec![Enemy::with_speed(0.0)])
+        .enemy_spawns(vec![Point2d::new(3, 4)])
+        .n_collectibles(0)
+        .seed(42)
+        .renderer(Box::new(GridRenderer::new(20, 13)))
+        .build();
+    game.init();
+
+    game.update();
+    assert_eq!(game.player_state(0).borrow().health(), 9);
+    assert!(game.player_state(0).borrow().is_invulnerable());
+    // pushed away from the enemy on the left
+    assert_eq!(game.player_position(0).round().to_u16(), Point2d::new(6, 4));
+
+    // a hit while invulnerable neither hurts nor pushes the player
+    game.player(0).borrow_mut().set_position(Point2d::new(3.0, 4.0));
+    game.update();
+    assert_eq!(game.player_state(0).borrow().health(), 9);
+    assert_eq!(game.player_position(0).round().to_u16(), Point2d::new(3, 4));
+    assert!(game.player(0).borrow().is_flashing());
+    game.update();
+    assert!(!game.player(0).borrow().is_flashing());
+
+    for _ in 0..3 {
+        game.update();
+    }
+    assert_eq!(game.player_state(0).borrow().health(), 8);
+    // pushed back against the direction the player faces, up to the wall
+    assert_eq!(game.player_position(0).round().to_u16(), Point2d::new(1, 4));
+
+    // frozen enemies do not hit
+    game.player_state(0)
+        .borrow_mut()
+        .collect(CollectibleKind::Freeze);
+    game.player(0).borrow_mut().set_position(Point2d::new(3.0, 4.0));
+    for _ in 0..10 {
+        game.update();
+    }
+    assert_eq!(game.player_state(0).borrow().health(), 8);
+    assert_eq!(game.player_position(0).round().to_u16(), Point2d::new(3, 4));
+}
+
+#[test]
+fn test_keys_move_between_the_screens() {
+    let mut game = Game::builder()
+        .width(60)
+        .height(20)
+        .player_starting_speed(0.0)
+        .enemies(vec![])
+        .seed(42)
+        .renderer(Box::new(GridRenderer::new(60, 23)))
+        .build();
+    game.init();
+    let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+    let shows = |game: &Game, text: &str| {
+        let grid = game.renderer::<GridRenderer>().unwrap();
+        (0..grid.height()).any(|y| grid.row(y).contains(text))
+    };
+
+    game.render(1.0);
+    assert!(shows(&game, "HIDE-N-SEEK"));
+
+    game.handle_key(key(KeyCode::Enter));
+    assert_eq!(game.state(), GameState::Playing);
+    game.handle_key(key(KeyCode::Up));
+    game.handle_key(key(KeyCode::Char('p')));
+    assert_eq!(game.state(), GameState::Paused);
+    game.render(1.0);
+    assert!(shows(&game, "PAUSED"));
+
+    // keys other than the screen keys do not reach the paused player
+    game.handle_key(key(KeyCode::Up));
+    assert_eq!(game.replay().events().len(), 1);
+
+    game.handle_key(key(KeyCode::Char('p')));
+    game.handle_key(key(KeyCode::Char('q')));
+    assert_eq!(game.state(), GameState::GameOver);
+    game.render(1.0);
+    assert!(shows(&game, "GAME OVER"));
+    assert!(shows(&game, "Score: 0"));
+
+    game.handle_key(key(KeyCode::Enter));
+    assert_eq!(game.state(), GameState::Finished(RunOutcome::Restart));
+}
+
+#[test]
+fn test_high_score_is_entered_on_game_over() {
+    let path = std::env::temp_dir().join("hide-n-seek-game-high-scores.json");
+    let _ = std::fs::remove_file(&path);
+    let path = path.to_str().unwrap();
+    let builder = || {
+        Game::builder()
+            .width(60)
+            .height(20)
+            .enemies(vec![])
+            .seed(42)
+            .map_name("arena")
+            .high_scores_path(path)
+            .renderer(Box::new(GridRenderer::new(60, 23)))
+    };
+    let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+
+    let mut game = builder().build();
+    game.init();
+    game.player_state(0).borrow_mut().increase_score();
+    game.handle_key(key(KeyCode::Enter));
+    game.handle_key(key(KeyCode::Char('q')));
+    assert_eq!(game.state(), GameState::GameOver);
+
+    // the quit key is part of the name while it is typed
+    for character in "Quinn!".chars() {
+        game.handle_key(key(KeyCode::Char(character)));
+    }
+    game.handle_key(key(KeyCode::Backspace));
+    game.handle_key(key(KeyCode::Enter));
+    assert_eq!(game.state(), GameState::GameOver);
+    game.handle_key(key(KeyCode::Enter));
+    assert_eq!(game.state(), GameState::Finished(RunOutcome::Restart));
+
+    let entries = HighScores::load(path).entries().to_vec();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].name, "Quinn");
+    assert_eq!(entries[0].score, 1);
+    assert_eq!(entries[0].map, "arena");
+
+    let mut game = builder().build();
+    game.handle_key(key(KeyCode::Char('t')));
+    assert_eq!(game.state(), GameState::HighScores);
+    game.render(1.0);
+    let grid = game.renderer::<GridRenderer>().unwrap();
+    assert!((0..grid.height()).any(|y| grid.row(y).contains("1. Quinn")));
+
+    // a score of zero does not make it onto the table
+    game.handle_key(key(KeyCode::Esc));
+    game.handle_key(key(KeyCode::Enter));
+    game.handle_key(key(KeyCode::Char('q')));
+    assert_eq!(game.state(), GameState::GameOver);
+    game.handle_key(key(KeyCode::Char('q')));
+    assert_eq!(game.state(), GameState::Finished(RunOutcome::Quit));
+
+    // a loaded game shows them once it is told where they are
+    let save = std::env::temp_dir().join("hide-n-seek-high-scores-save.json");
+    let save = save.to_str().unwrap();
+    builder().build().save(save).unwrap();
+    let mut loaded = Game::load(save).unwrap();
+    loaded.set_high_scores_path(path);
+    loaded.handle_key(key(KeyCode::Char('t')));
+    assert_eq!(loaded.high_scores().entries().len(), 1);
+}
+
+#[test]
+fn test_direct_controls_move_while_held() {
+    let builder = || {
+        Game::builder()
+            .width(30)
+            .height(20)
+            .enemies(vec![])
+            .collectible_weights(vec![(CollectibleKind::Coin, 1)])
+            .player_starting_position(10.0, 10.0)
+            .player_starting_speed(2.0)
+            .control_scheme(ControlScheme::Direct)
+            .update_interval(std::time::Duration::from_millis(280))
+            .seed(3)
+            .renderer(Box::new(GridRenderer::new(30, 23)))
+    };
+    let key = |code, kind| KeyEvent::new_with_kind(code, KeyModifiers::NONE, kind);
+
+    let mut game = builder().build();
+    game.init();
+    game.handle_key(key(KeyCode::Enter, KeyEventKind::Press));
+    game.update();
+    assert_eq!(game.player_position(0), Point2d::new(10.0, 10.0));
+
+    // up and left together move diagonally, and repeating a key changes
+    // nothing
+    game.handle_key(key(KeyCode::Up, KeyEventKind::Press));
+    game.handle_key(key(KeyCode::Char('a'), KeyEventKind::Press));
+    game.handle_key(key(KeyCode::Up, KeyEventKind::Repeat));
+    for _ in 0..5 {
+        game.update();
+    }
+    assert_eq!(game.player_position(0), Point2d::new(8.0, 8.0));
+
+    game.handle_key(key(KeyCode::Char('a'), KeyEventKind::Release));
+    game.handle_key(key(KeyCode::Up, KeyEventKind::Release));
+    game.update();
+    assert_eq!(game.player_position(0), Point2d::new(8.0, 8.0));
+    assert_eq!(game.replay().events().len(), 4);
+
+    let mut replayed = builder().build();
+    replayed.play_back(&game.replay());
+    assert_eq!(game, replayed);
+}
+
+#[test]
+fn test_game_is_over_once_every_player_died() {
+    let mut game = Game::builder()
+        .width(30)
+        .height(20)
+        .enemies(vec![])
+        .n_players(2)
+        .player_starting_position(10.0, 10.0)
+        .seed(5)
+        .renderer(Box::new(GridRenderer::new(30, 24)))
+        .build();
+    game.init();
+    game.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+
+    // each player turns with their own keys
+    let mut quit = false;
+    game.handle_key_event(
+        KeyEvent::new(KeyCode::Char('d'), KeyModifiers::NONE),
+        &mut quit,
+    );
+    game.handle_key_event(KeyEvent::new(KeyCode::Left, KeyModifiers::NONE), &mut quit);
+    assert_eq!(game.player_direction(0).round(), Point2d::new(1.0, 1.0));
+    assert_eq!(game.player_direction(1).round(), Point2d::new(1.0, -1.0));
+    let events = game.replay().events().to_vec();
+    assert_eq!((events[0].player, events[1].player), (0, 1));
+
+    game.update();
+    game.draw();
+    let grid = game.renderer::<GridRenderer>().unwrap();
+    assert!(grid.row(22).contains("P1: Health: 10"));
+    assert!(grid.row(23).contains("P2: Health: 10"));
+
+    while game.player_state(0).borrow().is_alive() {
+        game.player_state(0).borrow_mut().decrease_health();
+    }
+    game.check_game_over(None);
+    assert_eq!(game.state(), GameState::Playing);
+
+    while game.player_state(1).borrow().is_alive() {
+        game.player_state(1).borrow_mut().decrease_health();
+    }
+    game.check_game_over(None);
+    assert_eq!(game.state(), GameState::GameOver);
+}
+
+#[test]
+fn test_generated_map_stays_connected() {
+    let mut game = Game::builder()
+        .n_random_walls(30)
+        .map_generator(Box::new(crate::map::Maze))
+        .player_starting_position(1.0, 1.0)
+        .seed(5)
+        .renderer(Box::new(GridRenderer::new(80, 43)))
+        .build();
+    game.init();
+
+    // every open tile can be reached from the player start
+    let start = game.player_position(0).round().to_u16();
+    let mut reached = HashSet::from([start]);
+    let mut queue = VecDeque::from([start]);
+    while let Some(tile) = queue.pop_front() {
+        for next in [
+            Point2d::new(tile.x + 1, tile.y),
+            Point2d::new(tile.x, tile.y + 1),
+            Point2d::new(tile.x - 1, tile.y),
+            Point2d::new(tile.x, tile.y - 1),
+        ] {
+            if !game.is_blocked(next) && reached.insert(next) {
+                queue.push_back(next);
+            }
+        }
+    }
+    let open = (0..game.width)
+        .flat_map(|x| (0..game.height).map(move |y| Point2d::new(x, y)))
+        .filter(|&tile| !game.is_blocked(tile))
+        .count();
+    assert!(open > 1000);
+    assert_eq!(reached.len(), open);
+}
+
+/// Plays a game built by `builder` with a few key presses, then checks that
+/// the replay of it ends up in the same state.
+fn assert_replay_reaches_same_end_state(
+    builder: impl Fn() -> GameBuilder,
+    name: &str,
+) -> Result<(), Box<dyn Error>> {
+    let keys = [(3, KeyCode::Right), (4, KeyCode::Up), (10, KeyCode::Left)];
+
+    let mut game = builder().seed(7).build();
+    game.init();
+    let mut quit = false;
+    for tick in 0..20 {
+        for (_, code) in keys.iter().filter(|(key_tick, _)| *key_tick == tick) {
+            game.handle_key_event(KeyEvent::new(*code, KeyModifiers::NONE), &mut quit);
+        }
+        game.update();
+    }
+
+    let path = std::env::temp_dir().join(name);
+    let path = path.to_str().unwrap();
+    game.replay().save(path)?;
+    let replay = Replay::load(path)?;
+    assert_eq!(replay, game.replay());
+    assert_eq!(replay.events().len(), keys.len());
+
+    let mut replayed = builder().seed(replay.seed()).build();
+    replayed.play_back(&replay);
+    assert_eq!(game, replayed);
+    assert_eq!(replayed.replay(), replay);
+
+    Ok(())
+}
+
+#[test]
+fn test_replay_reaches_same_end_state() -> Result<(), Box<dyn Error>> {
+    assert_replay_reaches_same_end_state(
+        || {
+            Game::builder()
+                .n_random_walls(30)
+                .player_starting_speed(2.0)
+                .update_interval(std::time::Duration::from_millis(280))
+                .renderer(Box::new(GridRenderer::new(80, 51)))
+        },
+        "hide-n-seek-replay.json",
+    )
+}
+
+#[test]
+fn test_replay_of_a_generated_map() -> Result<(), Box<dyn Error>> {
+    assert_replay_reaches_same_end_state(
+        || {
+            Game::builder()
+                .map_generator(Box::new(crate::map::Caves::default()))
+                .player_starting_position(40.0, 24.0)
+                .player_starting_speed(2.0)
+                .update_interval(std::time::Duration::from_millis(280))
+                .renderer(Box::new(GridRenderer::new(80, 51)))
+        },
+        "hide-n-seek-map-replay.json",
+    )
+}
+
+#[test]
+fn test_loaded_game_continues_identically() -> Result<(), Box<dyn Error>> {
+    let mut game = Game::builder()
+        .n_random_walls(30)
+        .player_starting_speed(2.0)
+        .seed(3)
+        .renderer(Box::new(GridRenderer::new(80, 51)))
+        .build();
+    game.init();
+    for _ in 0..10 {
+        game.update();
+    }
+
+    let path = std::env::temp_dir().join("hide-n-seek-save.json");
+    let path = path.to_str().unwrap();
+    game.save(path)?;
+    let mut loaded = Game::load(path)?;
+    assert_eq!(game, loaded);
+
+    for _ in 0..10 {
+        game.update();
+        loaded.update();
+    }
+    assert_eq!(game, loaded);
+
+    Ok(())
+}
@@ -0,0 +1,58 @@
+mod tests;
+
+use std::time::Duration;
+
+use crate::point::Point2d;
+
+/// How many steps are simulated at most to catch up with the time that
+/// passed. When a frame takes longer than this, the game slows down instead
+/// of spending even more time on catching up.
+const MAX_STEPS_PER_ADVANCE: u32 = 10;
+
+/// Runs the simulation in steps of the same length, no matter how much time
+/// passes between two frames. The time that is left over after the last
+/// step is carried over to the next frame.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FixedTimestep {
+    step: Duration,
+    accumulator: Duration,
+}
+
+impl FixedTimestep {
+    pub fn new(step: Duration) -> Self {
+        Self {
+            step,
+            accumulator: Duration::ZERO,
+        }
+    }
+
+    /// Adds the time that passed and returns how many steps have to be
+    /// simulated now.
+    pub fn advance(&mut self, elapsed: Duration) -> u32 {
+        self.accumulator += elapsed;
+        let mut steps = 0;
+        while self.accumulator >= self.step && steps < MAX_STEPS_PER_ADVANCE {
+            self.accumulator -= self.step;
+            steps += 1;
+        }
+        if steps == MAX_STEPS_PER_ADVANCE {
+            self.accumulator = self.accumulator.min(self.step);
+        }
+        steps
+    }
+
+    /// How far the time is between the last step and the next one, from 0
+    /// to 1.
+    pub fn alpha(&self) -> f64 {
+        (self.accumulator.as_secs_f64() / self.step.as_secs_f64()).min(1.0)
+    }
+
+    pub fn until_next_step(&self) -> Duration {
+        self.step.saturating_sub(self.accumulator)
+    }
+}
+
+/// The position `alpha` of the way from `previous` to `current`.
+pub fn interpolate(previous: Point2d<f64>, current: Point2d<f64>, alpha: f64) -> Point2d<f64> {
+    previous + (current - previous) * alpha
+}
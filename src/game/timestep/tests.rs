@@ -0,0 +1,52 @@
+#![cfg(test)]
+
+use std::time::Duration;
+
+use crate::point::Point2d;
+
+use super::*;
+
+#[test]
+fn test_steps_do_not_depend_on_the_frame_length() {
+    let step = Duration::from_millis(100);
+    let mut slow = FixedTimestep::new(step);
+    let mut fast = FixedTimestep::new(step);
+
+    let slow_steps: u32 = (0..4).map(|_| slow.advance(Duration::from_millis(250))).sum();
+    let fast_steps: u32 = (0..100).map(|_| fast.advance(Duration::from_millis(10))).sum();
+
+    assert_eq!(slow_steps, 10);
+    assert_eq!(fast_steps, 10);
+    assert_eq!(slow.alpha(), 0.0);
+}
+
+#[test]
+fn test_left_over_time_is_carried_over() {
+    let mut timestep = FixedTimestep::new(Duration::from_millis(100));
+
+    assert_eq!(timestep.advance(Duration::from_millis(130)), 1);
+    assert!((timestep.alpha() - 0.3).abs() < 1e-9);
+    assert_eq!(timestep.until_next_step(), Duration::from_millis(70));
+    assert_eq!(timestep.advance(Duration::from_millis(70)), 1);
+    assert_eq!(timestep.alpha(), 0.0);
+}
+
+#[test]
+fn test_long_stalls_are_not_caught_up_completely() {
+    let mut timestep = FixedTimestep::new(Duration::from_millis(100));
+
+    assert_eq!(timestep.advance(Duration::from_secs(60)), MAX_STEPS_PER_ADVANCE);
+    assert_eq!(timestep.advance(Duration::ZERO), 1);
+    assert_eq!(timestep.advance(Duration::ZERO), 0);
+}
+
+#[test]
+fn test_interpolate() {
+    let previous = Point2d::new(2.0, 4.0);
+    let current = Point2d::new(4.0, 0.0);
+
+    assert_eq!(interpolate(previous, current, 0.0), previous);
+    assert_eq!(interpolate(previous, current, 1.0), current);
+    let halfway = interpolate(previous, current, 0.5);
+    assert_eq!((halfway.x, halfway.y), (3.0, 2.0));
+}
@@ -1,37 +1,92 @@
 use serde::{Deserialize, Serialize};
 
 use crate::traits::UpdatableByTimeFrame;
+use crate::unit::StatusEffect;
 use crate::{point::Point2d, traits::Position};
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct Hud {
+/// What the HUD shows about one player.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct HudSection {
     score: u32,
     health: u8,
+    effects: Vec<StatusEffect>,
+}
+
+impl HudSection {
+    pub fn text(&self) -> String {
+        let mut text = format!("Health: {}, Score: {}", self.health, self.score);
+        for effect in &self.effects {
+            text += &format!(", {}", effect.kind());
+            if effect.stacks() > 1 {
+                text += &format!(" x{}", effect.stacks());
+            }
+            text += &format!(" ({})", effect.ticks_left());
+        }
+        text
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Hud {
+    /// One section for each player, shown on a line of its own.
+    sections: Vec<HudSection>,
     position: Point2d<u16>,
+    #[serde(skip)]
+    message: String,
 }
 
 impl Hud {
     pub fn new(position: Point2d<u16>) -> Self {
         Self {
-            score: 0,
-            health: 0,
+            sections: vec![],
             position,
+            message: String::new(),
+        }
+    }
+
+    /// The line of each player, numbered if there are several of them. The
+    /// message follows the first line.
+    pub fn lines(&self) -> Vec<String> {
+        let numbered = self.sections.len() > 1;
+        let mut lines: Vec<String> = self
+            .sections
+            .iter()
+            .enumerate()
+            .map(|(number, section)| match numbered {
+                true => format!("P{}: {}", number + 1, section.text()),
+                false => section.text(),
+            })
+            .collect();
+        if let Some(first) = lines.first_mut().filter(|_| !self.message.is_empty()) {
+            *first = format!("{}  {}", first, self.message);
         }
+        lines
     }
 
     pub fn text(&self) -> String {
-        format!("Health: {}, Score: {}", self.health, self.score)
+        self.lines().join("\n")
+    }
+
+    pub fn set_message(&mut self, message: String) {
+        self.message = message;
     }
 
-    pub fn set(&mut self, score: u32, health: u8) {
-        self.score = score;
-        self.health = health;
+    /// Shows the score, health and effects of each player in turn.
+    pub fn set(&mut self, players: impl IntoIterator<Item = (u32, u8, Vec<StatusEffect>)>) {
+        self.sections = players
+            .into_iter()
+            .map(|(score, health, effects)| HudSection {
+                score,
+                health,
+                effects,
+            })
+            .collect();
     }
 }
 
 impl Position<u16> for Hud {
     fn position(&self) -> crate::point::Point2d<u16> {
-        self.position.clone()
+        self.position
     }
 
     fn set_position(&mut self, position: crate::point::Point2d<u16>) {
@@ -41,7 +96,10 @@ impl Position<u16> for Hud {
 
 impl UpdatableByTimeFrame for Hud {
     fn update(&mut self, game: &crate::game::Game) {
-        let player_state = game.player_state().borrow();
-        self.set(player_state.score(), player_state.health());
+        self.set((0..game.n_players()).map(|number| {
+            let state = game.player_state(number).borrow();
+            let effects = state.effects().iter().copied().collect();
+            (state.score(), state.health(), effects)
+        }));
     }
 }
@@ -1,33 +1,68 @@
+pub mod bindings;
+pub mod held;
+
 use std::time::Duration;
 
-use crossterm::event::{poll, Event, KeyCode, KeyEvent, KeyModifiers, KeyEventKind};
+use clap::ValueEnum;
+use crossterm::event::{poll, Event};
+use serde::{Deserialize, Serialize};
 
 use crate::unit::Player;
 
-pub fn poll_key_event(duration: Duration) -> Option<KeyEvent> {
+pub use bindings::{Action, BindingError, Bindings, Key};
+pub use held::HeldKeys;
+
+/// How the movement keys steer the player.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+pub enum ControlScheme {
+    /// Left and right turn the player by 45°, up and down change its speed
+    #[default]
+    Tank,
+    /// The arrows and the diagonals move the player that way while they are
+    /// held
+    Direct,
+}
+
+/// Reads a key or resize event if one is available within `duration`. Key
+/// presses, repeats and releases are all returned, although most terminals
+/// only report presses. The other events are dropped.
+pub fn poll_event(duration: Duration) -> Option<Event> {
     if poll(duration).ok()? {
         let event = crossterm::event::read().ok()?;
-        if let Event::Key(key_event) = event {
-            if key_event.kind == KeyEventKind::Press {
-                return Some(key_event);
-            }
+        if let Event::Key(_) | Event::Resize(_, _) = event {
+            return Some(event);
         }
     }
     None
 }
 
-pub fn handle_key_event(key: KeyEvent, player: &mut Player, quit: &mut bool) {
-    match key.code {
-        KeyCode::Left => player.turn_left(),
-        KeyCode::Right => player.turn_right(),
-        KeyCode::Up => player.accelerate(),
-        KeyCode::Down => player.decelerate(),
-        KeyCode::Char('q') | KeyCode::Esc => *quit = true,
-        KeyCode::Char('c') => {
-            if key.modifiers == KeyModifiers::CONTROL {
-                *quit = true;
-            }
-        }
+/// Waits until an event is available or `timeout` passed, without reading
+/// the event.
+pub fn wait_for_event(timeout: Duration) {
+    let _ = poll(timeout);
+}
+
+/// Applies a movement action to the player. Quitting sets `quit`, the
+/// other actions are handled by the game.
+pub fn handle_action(action: Action, player: &mut Player, quit: &mut bool) {
+    if let (ControlScheme::Direct, Some(direction)) = (player.control_scheme(), action.direction()) {
+        player.hold(direction);
+        return;
+    }
+    match action {
+        Action::Quit => *quit = true,
+        Action::TurnLeft => player.turn_left(),
+        Action::TurnRight => player.turn_right(),
+        Action::Accelerate => player.accelerate(),
+        Action::Decelerate => player.decelerate(),
         _ => {}
     }
 }
+
+/// Stops moving the player in the direction of `action` once its key is
+/// let go, with the direct control scheme.
+pub fn handle_release(action: Action, player: &mut Player) {
+    if let (ControlScheme::Direct, Some(direction)) = (player.control_scheme(), action.direction()) {
+        player.release(direction);
+    }
+}
@@ -0,0 +1,450 @@
+mod tests;
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    error::Error,
+    fmt::{self, Display, Formatter},
+    fs,
+    path::PathBuf,
+    str::FromStr,
+};
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::{Deserialize, Serialize};
+
+use crate::point::Point2d;
+
+/// How many players can share the keyboard with the default keys.
+pub const MAX_PLAYERS: usize = 4;
+
+/// Something the player can do with a key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Action {
+    TurnLeft,
+    TurnRight,
+    Accelerate,
+    Decelerate,
+    /// Moves up and to the left, only with the direct control scheme.
+    UpLeft,
+    UpRight,
+    DownLeft,
+    DownRight,
+    Pause,
+    /// Gives up the game, or goes back from a screen.
+    Quit,
+    Save,
+    /// Starts, resumes or restarts the game.
+    Confirm,
+    HighScores,
+}
+
+impl Action {
+    pub const ALL: [Action; 13] = [
+        Action::TurnLeft,
+        Action::TurnRight,
+        Action::Accelerate,
+        Action::Decelerate,
+        Action::UpLeft,
+        Action::UpRight,
+        Action::DownLeft,
+        Action::DownRight,
+        Action::Pause,
+        Action::Quit,
+        Action::Save,
+        Action::Confirm,
+        Action::HighScores,
+    ];
+
+    /// The keys the action is bound to unless the config says otherwise:
+    /// the arrows, WASD and the vim keys for moving, and the keys around the
+    /// vim keys or the ones above and below the arrows for the diagonals.
+    pub fn default_keys(self) -> Vec<Key> {
+        let keys: &[&str] = match self {
+            Action::TurnLeft => &["Left", "a", "h"],
+            Action::TurnRight => &["Right", "d", "l"],
+            Action::Accelerate => &["Up", "w", "k"],
+            Action::Decelerate => &["Down", "s", "j"],
+            Action::UpLeft => &["Home", "y"],
+            Action::UpRight => &["PageUp", "u"],
+            Action::DownLeft => &["End", "b"],
+            Action::DownRight => &["PageDown", "n"],
+            Action::Pause => &["p"],
+            Action::Quit => &["q", "Esc", "Ctrl+c"],
+            Action::Save => &["Ctrl+s"],
+            Action::Confirm => &["Enter", "Space"],
+            Action::HighScores => &["t"],
+        };
+        keys.iter()
+            .map(|key| key.parse().expect("the default keys are valid"))
+            .collect()
+    }
+
+    /// The keys the action is bound to for `player` when several players
+    /// share the keyboard. Each of them moves with their own keys: WASD,
+    /// the arrows, the vim keys and the number keys. The first player also
+    /// has the keys that are not about moving.
+    pub fn default_player_keys(self, player: usize) -> Vec<Key> {
+        if self.direction().is_none() {
+            return if player == 0 {
+                self.default_keys()
+            } else {
+                vec![]
+            };
+        }
+        let keys: &[&str] = match (player, self) {
+            (0, Action::TurnLeft) => &["a"],
+            (0, Action::TurnRight) => &["d"],
+            (0, Action::Accelerate) => &["w"],
+            (0, Action::Decelerate) => &["s"],
+            (1, Action::TurnLeft) => &["Left"],
+            (1, Action::TurnRight) => &["Right"],
+            (1, Action::Accelerate) => &["Up"],
+            (1, Action::Decelerate) => &["Down"],
+            (1, Action::UpLeft) => &["Home"],
+            (1, Action::UpRight) => &["PageUp"],
+            (1, Action::DownLeft) => &["End"],
+            (1, Action::DownRight) => &["PageDown"],
+            (2, Action::TurnLeft) => &["h"],
+            (2, Action::TurnRight) => &["l"],
+            (2, Action::Accelerate) => &["k"],
+            (2, Action::Decelerate) => &["j"],
+            (2, Action::UpLeft) => &["y"],
+            (2, Action::UpRight) => &["u"],
+            (2, Action::DownLeft) => &["b"],
+            (2, Action::DownRight) => &["n"],
+            (3, Action::TurnLeft) => &["4"],
+            (3, Action::TurnRight) => &["6"],
+            (3, Action::Accelerate) => &["8"],
+            (3, Action::Decelerate) => &["2"],
+            (3, Action::UpLeft) => &["7"],
+            (3, Action::UpRight) => &["9"],
+            (3, Action::DownLeft) => &["1"],
+            (3, Action::DownRight) => &["3"],
+            _ => &[],
+        };
+        keys.iter()
+            .map(|key| key.parse().expect("the default keys are valid"))
+            .collect()
+    }
+
+    /// The direction the action moves the player in with the direct
+    /// control scheme, where turning left and right move west and east and
+    /// accelerating and decelerating move north and south.
+    pub fn direction(self) -> Option<Point2d<f64>> {
+        let (x, y) = match self {
+            Action::TurnLeft => (-1.0, 0.0),
+            Action::TurnRight => (1.0, 0.0),
+            Action::Accelerate => (0.0, -1.0),
+            Action::Decelerate => (0.0, 1.0),
+            Action::UpLeft => (-1.0, -1.0),
+            Action::UpRight => (1.0, -1.0),
+            Action::DownLeft => (-1.0, 1.0),
+            Action::DownRight => (1.0, 1.0),
+            _ => return None,
+        };
+        Some(Point2d::new(x, y))
+    }
+}
+
+impl Display for Action {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Action::TurnLeft => "turn left",
+            Action::TurnRight => "turn right",
+            Action::Accelerate => "accelerate",
+            Action::Decelerate => "decelerate",
+            Action::UpLeft => "up left",
+            Action::UpRight => "up right",
+            Action::DownLeft => "down left",
+            Action::DownRight => "down right",
+            Action::Pause => "pause",
+            Action::Quit => "quit",
+            Action::Save => "save",
+            Action::Confirm => "confirm",
+            Action::HighScores => "high scores",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// A key press as far as the bindings are concerned. Letters are matched
+/// regardless of shift and caps lock, and Ctrl is the only modifier that
+/// counts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Key {
+    code: KeyCode,
+    control: bool,
+}
+
+impl Key {
+    pub fn new(code: KeyCode, control: bool) -> Self {
+        let code = match code {
+            KeyCode::Char(character) => KeyCode::Char(character.to_ascii_lowercase()),
+            code => code,
+        };
+        Self { code, control }
+    }
+}
+
+impl From<&KeyEvent> for Key {
+    fn from(event: &KeyEvent) -> Self {
+        Self::new(event.code, event.modifiers.contains(KeyModifiers::CONTROL))
+    }
+}
+
+const NAMED_KEYS: [(&str, KeyCode); 13] = [
+    ("Left", KeyCode::Left),
+    ("Right", KeyCode::Right),
+    ("Up", KeyCode::Up),
+    ("Down", KeyCode::Down),
+    ("Enter", KeyCode::Enter),
+    ("Esc", KeyCode::Esc),
+    ("Space", KeyCode::Char(' ')),
+    ("Tab", KeyCode::Tab),
+    ("Backspace", KeyCode::Backspace),
+    ("Home", KeyCode::Home),
+    ("End", KeyCode::End),
+    ("PageUp", KeyCode::PageUp),
+    ("PageDown", KeyCode::PageDown),
+];
+
+impl FromStr for Key {
+    type Err = BindingError;
+
+    /// Parses a key like `a`, `Left`, `F2` or `Ctrl+s`. Names are not case
+    /// sensitive.
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let unknown = || BindingError::UnknownKey(text.to_string());
+        let (control, name) = match text.split_once('+') {
+            Some((modifier, name)) if modifier.eq_ignore_ascii_case("ctrl") && !name.is_empty() => {
+                (true, name)
+            }
+            _ => (false, text),
+        };
+
+        let mut characters = name.chars();
+        let code = match (characters.next(), characters.next()) {
+            (Some(character), None) => KeyCode::Char(character),
+            _ => {
+                let named = NAMED_KEYS
+                    .iter()
+                    .find(|(key_name, _)| key_name.eq_ignore_ascii_case(name))
+                    .map(|(_, code)| *code);
+                let function = name
+                    .strip_prefix(['F', 'f'])
+                    .and_then(|number| number.parse().ok())
+                    .filter(|number| (1..=12).contains(number))
+                    .map(KeyCode::F);
+                named.or(function).ok_or_else(unknown)?
+            }
+        };
+        Ok(Self::new(code, control))
+    }
+}
+
+impl Display for Key {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.control {
+            write!(f, "Ctrl+")?;
+        }
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "Space"),
+            KeyCode::Char(character) => write!(f, "{}", character),
+            KeyCode::F(number) => write!(f, "F{}", number),
+            code => match NAMED_KEYS.iter().find(|(_, named)| *named == code) {
+                Some((name, _)) => write!(f, "{}", name),
+                None => write!(f, "{:?}", code),
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum BindingError {
+    UnknownKey(String),
+    /// The same key is bound to two actions.
+    Conflict {
+        key: Key,
+        first: Action,
+        second: Action,
+    },
+    /// The same key is bound for two players, counting from 0.
+    SharedKey {
+        key: Key,
+        first_player: usize,
+        second_player: usize,
+    },
+}
+
+impl Display for BindingError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            BindingError::UnknownKey(key) => write!(
+                f,
+                "unknown key {:?}, expected a character, a name like \"Left\" or \"F2\", or \"Ctrl+\" and one of them",
+                key
+            ),
+            BindingError::Conflict { key, first, second } => {
+                write!(f, "`{}` is bound to both {} and {}", key, first, second)
+            }
+            BindingError::SharedKey {
+                key,
+                first_player,
+                second_player,
+            } => write!(
+                f,
+                "`{}` is bound for both player {} and player {}",
+                key,
+                first_player + 1,
+                second_player + 1
+            ),
+        }
+    }
+}
+
+impl Error for BindingError {}
+
+/// The overrides of a config file, either for the first player or for
+/// each player in turn.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Config {
+    Player(BTreeMap<Action, Vec<String>>),
+    Players(Vec<BTreeMap<Action, Vec<String>>>),
+}
+
+/// Which action each key stands for.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bindings {
+    actions: HashMap<Key, Action>,
+}
+
+impl Default for Bindings {
+    /// The bindings of a single player.
+    fn default() -> Self {
+        let table = Action::ALL.map(|action| (action, action.default_keys()));
+        Self::new(table).expect("the default keys do not conflict")
+    }
+}
+
+impl Bindings {
+    /// Binds every action to its keys. A key can only stand for one action.
+    pub fn new(table: impl IntoIterator<Item = (Action, Vec<Key>)>) -> Result<Self, BindingError> {
+        let mut actions = HashMap::new();
+        for (action, keys) in table {
+            for key in keys {
+                match actions.insert(key, action) {
+                    Some(first) if first != action => {
+                        return Err(BindingError::Conflict {
+                            key,
+                            first,
+                            second: action,
+                        })
+                    }
+                    _ => {}
+                }
+            }
+        }
+        Ok(Self { actions })
+    }
+
+    /// The bindings of `players` players sharing the keyboard. A single
+    /// player gets every default key.
+    pub fn defaults(players: usize) -> Vec<Self> {
+        (0..players)
+            .map(|player| {
+                Self::new(Self::default_table(player, players))
+                    .expect("the default keys do not conflict")
+            })
+            .collect()
+    }
+
+    fn default_table(player: usize, players: usize) -> BTreeMap<Action, Vec<Key>> {
+        Action::ALL
+            .iter()
+            .map(|action| match players {
+                1 => (*action, action.default_keys()),
+                _ => (*action, action.default_player_keys(player)),
+            })
+            .collect()
+    }
+
+    /// Checks that no key is bound for two of the `players`.
+    pub fn check_shared_keys(players: &[Bindings]) -> Result<(), BindingError> {
+        for (second_player, second) in players.iter().enumerate() {
+            for (first_player, first) in players[..second_player].iter().enumerate() {
+                if let Some(key) = second
+                    .actions
+                    .keys()
+                    .find(|key| first.actions.contains_key(key))
+                {
+                    return Err(BindingError::SharedKey {
+                        key: *key,
+                        first_player,
+                        second_player,
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Where the bindings are loaded from unless another file is given, in
+    /// the config directory of the user.
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("hide-n-seek").join("keys.json"))
+    }
+
+    /// Parses the bindings of `players` players from a JSON object from
+    /// action names to the keys they are bound to, e.g. `{"pause": ["p",
+    /// "Space"], "confirm": ["Enter"]}`, or from a list of such objects, one
+    /// for each player. The object alone is for the first player. The
+    /// actions that are left out keep their default keys.
+    pub fn parse(text: &str, players: usize) -> Result<Vec<Self>, Box<dyn Error>> {
+        let configs = match serde_json::from_str(text)? {
+            Config::Player(config) => vec![config],
+            Config::Players(configs) => configs,
+        };
+        let mut bindings = vec![];
+        for player in 0..players {
+            let mut table = Self::default_table(player, players);
+            for (action, keys) in configs.get(player).into_iter().flatten() {
+                let keys = keys
+                    .iter()
+                    .map(|key| key.parse())
+                    .collect::<Result<_, _>>()?;
+                table.insert(*action, keys);
+            }
+            bindings.push(Self::new(table)?);
+        }
+        Self::check_shared_keys(&bindings)?;
+        Ok(bindings)
+    }
+
+    pub fn load(path: &str, players: usize) -> Result<Vec<Self>, Box<dyn Error>> {
+        Self::parse(&fs::read_to_string(path)?, players)
+    }
+
+    /// Loads the bindings from the default path, or uses the defaults if
+    /// there is no file there.
+    pub fn load_default(players: usize) -> Result<Vec<Self>, Box<dyn Error>> {
+        match Self::default_path().filter(|path| path.exists()) {
+            Some(path) => Self::load(&path.to_string_lossy(), players),
+            None => Ok(Self::defaults(players)),
+        }
+    }
+
+    /// The action `key` stands for, if any.
+    pub fn action(&self, key: &KeyEvent) -> Option<Action> {
+        self.actions.get(&Key::from(key)).copied()
+    }
+
+    /// The keys bound to `action`, in no particular order.
+    pub fn keys(&self, action: Action) -> impl Iterator<Item = Key> + '_ {
+        self.actions
+            .iter()
+            .filter(move |(_, bound)| **bound == action)
+            .map(|(key, _)| *key)
+    }
+}
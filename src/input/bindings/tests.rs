@@ -0,0 +1,137 @@
+#![cfg(test)]
+
+use super::*;
+
+fn key(code: KeyCode) -> KeyEvent {
+    KeyEvent::new(code, KeyModifiers::NONE)
+}
+
+#[test]
+fn test_default_bindings() {
+    let bindings = Bindings::default();
+
+    for code in [KeyCode::Up, KeyCode::Char('w'), KeyCode::Char('k')] {
+        assert_eq!(bindings.action(&key(code)), Some(Action::Accelerate));
+    }
+    for code in [KeyCode::Left, KeyCode::Char('a'), KeyCode::Char('h')] {
+        assert_eq!(bindings.action(&key(code)), Some(Action::TurnLeft));
+    }
+    assert_eq!(
+        bindings.action(&KeyEvent::new(KeyCode::Char('W'), KeyModifiers::SHIFT)),
+        Some(Action::Accelerate)
+    );
+    assert_eq!(
+        bindings.action(&KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL)),
+        Some(Action::Quit)
+    );
+    assert_eq!(bindings.action(&key(KeyCode::Char('c'))), None);
+    assert_eq!(
+        bindings.action(&key(KeyCode::Char('s'))),
+        Some(Action::Decelerate)
+    );
+}
+
+#[test]
+fn test_keys_are_parsed_and_displayed() {
+    let parsed: Key = "ctrl+S".parse().unwrap();
+    assert_eq!(parsed, Key::new(KeyCode::Char('s'), true));
+    assert_eq!(parsed.to_string(), "Ctrl+s");
+
+    for text in ["a", "Left", "Space", "Esc", "F2", "Ctrl+Enter", "+"] {
+        assert_eq!(text.parse::<Key>().unwrap().to_string(), text);
+    }
+    assert_eq!(
+        "F13".parse::<Key>(),
+        Err(BindingError::UnknownKey("F13".to_string()))
+    );
+    assert!("Insert".parse::<Key>().is_err());
+}
+
+#[test]
+fn test_config_overrides_the_defaults() {
+    let bindings = Bindings::parse(r#"{"pause": ["Space", "p"], "confirm": ["Enter"]}"#, 1)
+        .unwrap()
+        .remove(0);
+
+    assert_eq!(
+        bindings.action(&key(KeyCode::Char(' '))),
+        Some(Action::Pause)
+    );
+    assert_eq!(bindings.action(&key(KeyCode::Enter)), Some(Action::Confirm));
+    // the actions that are left out keep their keys
+    assert_eq!(
+        bindings.action(&key(KeyCode::Char('d'))),
+        Some(Action::TurnRight)
+    );
+
+    let mut keys: Vec<String> = bindings
+        .keys(Action::Pause)
+        .map(|key| key.to_string())
+        .collect();
+    keys.sort();
+    assert_eq!(keys, ["Space", "p"]);
+}
+
+#[test]
+fn test_conflicting_keys_are_rejected() {
+    let error = Bindings::parse(r#"{"pause": ["a"]}"#, 1).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "`a` is bound to both turn left and pause"
+    );
+
+    let error = Bindings::new([
+        (Action::Save, vec![Key::new(KeyCode::F(2), false)]),
+        (Action::Quit, vec![Key::new(KeyCode::F(2), false)]),
+    ])
+    .unwrap_err();
+    assert_eq!(
+        error,
+        BindingError::Conflict {
+            key: Key::new(KeyCode::F(2), false),
+            first: Action::Save,
+            second: Action::Quit,
+        }
+    );
+
+    assert!(Bindings::parse(r#"{"jump": ["Space"]}"#, 1).is_err());
+    assert!(Bindings::parse(r#"{"pause": ["Pause"]}"#, 1).is_err());
+}
+
+#[test]
+fn test_players_share_the_keyboard() {
+    let players = Bindings::defaults(3);
+    assert_eq!(Bindings::check_shared_keys(&players), Ok(()));
+
+    assert_eq!(
+        players[0].action(&key(KeyCode::Char('a'))),
+        Some(Action::TurnLeft)
+    );
+    assert_eq!(
+        players[0].action(&key(KeyCode::Char('p'))),
+        Some(Action::Pause)
+    );
+    assert_eq!(
+        players[1].action(&key(KeyCode::Left)),
+        Some(Action::TurnLeft)
+    );
+    assert_eq!(players[1].action(&key(KeyCode::Char('p'))), None);
+    assert_eq!(
+        players[2].action(&key(KeyCode::Char('y'))),
+        Some(Action::UpLeft)
+    );
+    assert_eq!(players[0].action(&key(KeyCode::Left)), None);
+
+    let players = Bindings::parse(r#"[{}, {"turn_left": ["x"]}]"#, 2).unwrap();
+    assert_eq!(
+        players[1].action(&key(KeyCode::Char('x'))),
+        Some(Action::TurnLeft)
+    );
+    assert_eq!(players[1].action(&key(KeyCode::Left)), None);
+
+    let error = Bindings::parse(r#"[{}, {"pause": ["w"]}]"#, 2).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "`w` is bound for both player 1 and player 2"
+    );
+}
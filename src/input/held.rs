@@ -0,0 +1,77 @@
+mod tests;
+
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use super::Action;
+
+/// How long a key counts as held after it was pressed, if the terminal does
+/// not report releases. It is longer than the usual delay before a held key
+/// starts repeating.
+pub const FIRST_HOLD_TIMEOUT: Duration = Duration::from_millis(600);
+
+/// How long a key counts as held after it repeated, if the terminal does not
+/// report releases.
+pub const REPEAT_HOLD_TIMEOUT: Duration = Duration::from_millis(150);
+
+/// The keys that are held down, by the action they are bound to.
+///
+/// Most terminals only report key presses, which repeat while a key is
+/// held. Until a release is reported, a key is let go once it was not
+/// repeated for a while instead.
+#[derive(Debug, Default, Clone)]
+pub struct HeldKeys {
+    /// When each held key lets go unless it repeats.
+    deadlines: HashMap<Action, Instant>,
+    reports_releases: bool,
+}
+
+impl HeldKeys {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Holds the key of `action`, which was pressed or repeated at `now`.
+    /// Returns whether it was not held before.
+    pub fn press(&mut self, action: Action, now: Instant) -> bool {
+        let timeout = if self.deadlines.contains_key(&action) {
+            REPEAT_HOLD_TIMEOUT
+        } else {
+            FIRST_HOLD_TIMEOUT
+        };
+        self.deadlines.insert(action, now + timeout).is_none()
+    }
+
+    /// Lets go of the key of `action`, which the terminal reported as
+    /// released. From then on, keys are only let go when they are released.
+    /// Returns whether it was held.
+    pub fn release(&mut self, action: Action) -> bool {
+        self.reports_releases = true;
+        self.deadlines.remove(&action).is_some()
+    }
+
+    pub fn is_held(&self, action: Action) -> bool {
+        self.deadlines.contains_key(&action)
+    }
+
+    /// Lets go of the keys that timed out by `now` and returns their
+    /// actions.
+    pub fn expire(&mut self, now: Instant) -> Vec<Action> {
+        if self.reports_releases {
+            return vec![];
+        }
+        let mut expired: Vec<Action> = self
+            .deadlines
+            .iter()
+            .filter(|(_, deadline)| **deadline <= now)
+            .map(|(action, _)| *action)
+            .collect();
+        expired.sort();
+        expired.iter().for_each(|action| {
+            self.deadlines.remove(action);
+        });
+        expired
+    }
+}
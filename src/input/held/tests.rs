@@ -0,0 +1,36 @@
+#![cfg(test)]
+
+use super::*;
+
+#[test]
+fn test_keys_time_out_without_releases() {
+    let start = Instant::now();
+    let mut held = HeldKeys::new();
+
+    assert!(held.press(Action::TurnLeft, start));
+    assert!(held.expire(start + FIRST_HOLD_TIMEOUT / 2).is_empty());
+
+    // the key repeats, which keeps it held for a shorter while each time
+    let repeat = start + FIRST_HOLD_TIMEOUT / 2;
+    assert!(!held.press(Action::TurnLeft, repeat));
+    assert!(held.expire(repeat + REPEAT_HOLD_TIMEOUT / 2).is_empty());
+    assert_eq!(
+        held.expire(repeat + REPEAT_HOLD_TIMEOUT),
+        vec![Action::TurnLeft]
+    );
+    assert!(!held.is_held(Action::TurnLeft));
+}
+
+#[test]
+fn test_released_keys_do_not_time_out() {
+    let start = Instant::now();
+    let mut held = HeldKeys::new();
+
+    held.press(Action::UpLeft, start);
+    held.press(Action::Accelerate, start);
+    assert!(held.release(Action::UpLeft));
+    assert!(!held.release(Action::UpLeft));
+
+    assert!(held.expire(start + FIRST_HOLD_TIMEOUT * 10).is_empty());
+    assert!(held.is_held(Action::Accelerate));
+}
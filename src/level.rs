@@ -0,0 +1,196 @@
+mod tests;
+
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+    fs,
+};
+
+use crate::{point::Point2d, unit::Wall};
+
+/// A hand-authored map, parsed from an ASCII grid:
+///
+/// ```text
+/// #######
+/// #P  *E#
+/// # ### #
+/// #E    #
+/// #######
+/// ```
+///
+/// `#` is a wall, `E` an enemy spawn, `P` the player start, `*` the
+/// collectible and `.` or a space an empty tile. The outermost rows and
+/// columns always become walls, so only `#` and empty tiles may be placed
+/// there. Lines shorter than the longest one are padded with empty tiles.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Level {
+    width: u16,
+    height: u16,
+    walls: Vec<Point2d<u16>>,
+    enemy_spawns: Vec<Point2d<u16>>,
+    player_start: Point2d<u16>,
+    collectible: Option<Point2d<u16>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum LevelError {
+    UnexpectedCharacter {
+        line: usize,
+        column: usize,
+        character: char,
+    },
+    OnTheBorder {
+        line: usize,
+        column: usize,
+        character: char,
+    },
+    DuplicatePlayer {
+        line: usize,
+        column: usize,
+    },
+    DuplicateCollectible {
+        line: usize,
+        column: usize,
+    },
+    MissingPlayer,
+    TooSmall,
+    TooLarge,
+}
+
+impl Display for LevelError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            LevelError::UnexpectedCharacter {
+                line,
+                column,
+                character,
+            } => write!(
+                f,
+                "line {}, column {}: unexpected character {:?}, expected one of '#', 'E', 'P', '*', '.' or ' '",
+                line, column, character
+            ),
+            LevelError::OnTheBorder {
+                line,
+                column,
+                character,
+            } => write!(
+                f,
+                "line {}, column {}: {:?} can not be placed on the surrounding wall",
+                line, column, character
+            ),
+            LevelError::DuplicatePlayer { line, column } => write!(
+                f,
+                "line {}, column {}: the player start is already placed",
+                line, column
+            ),
+            LevelError::DuplicateCollectible { line, column } => write!(
+                f,
+                "line {}, column {}: the collectible is already placed",
+                line, column
+            ),
+            LevelError::MissingPlayer => write!(f, "the level has no player start 'P'"),
+            LevelError::TooSmall => write!(f, "the level must be at least 3x3 tiles"),
+            LevelError::TooLarge => write!(f, "the level must be at most {0}x{0} tiles", u16::MAX),
+        }
+    }
+}
+
+impl Error for LevelError {}
+
+impl Level {
+    pub fn load(path: &str) -> Result<Self, Box<dyn Error>> {
+        let text = fs::read_to_string(path)?;
+        Ok(Self::parse(&text)?)
+    }
+
+    pub fn parse(text: &str) -> Result<Self, LevelError> {
+        let lines: Vec<&str> = text.lines().collect();
+        let width = lines.iter().map(|line| line.chars().count()).max().unwrap_or(0);
+        let height = lines.len();
+        if width < 3 || height < 3 {
+            return Err(LevelError::TooSmall);
+        }
+        let width = u16::try_from(width).map_err(|_| LevelError::TooLarge)?;
+        let height = u16::try_from(height).map_err(|_| LevelError::TooLarge)?;
+
+        let mut walls = vec![];
+        let mut enemy_spawns = vec![];
+        let mut player_start = None;
+        let mut collectible = None;
+
+        for (y, line) in (0..height).zip(&lines) {
+            for (x, character) in (0..width).zip(line.chars()) {
+                let position = Point2d::new(x, y);
+                let (line, column) = (y as usize + 1, x as usize + 1);
+                let on_the_border = x == 0 || y == 0 || x == width - 1 || y == height - 1;
+
+                match character {
+                    '.' | ' ' => continue,
+                    // the game surrounds itself with walls
+                    '#' if on_the_border => continue,
+                    '#' => walls.push(position),
+                    'E' | 'P' | '*' if on_the_border => {
+                        return Err(LevelError::OnTheBorder {
+                            line,
+                            column,
+                            character,
+                        })
+                    }
+                    'E' => enemy_spawns.push(position),
+                    'P' if player_start.is_some() => {
+                        return Err(LevelError::DuplicatePlayer { line, column })
+                    }
+                    'P' => player_start = Some(position),
+                    '*' if collectible.is_some() => {
+                        return Err(LevelError::DuplicateCollectible { line, column })
+                    }
+                    '*' => collectible = Some(position),
+                    _ => {
+                        return Err(LevelError::UnexpectedCharacter {
+                            line,
+                            column,
+                            character,
+                        })
+                    }
+                }
+            }
+        }
+
+        Ok(Self {
+            width,
+            height,
+            walls,
+            enemy_spawns,
+            player_start: player_start.ok_or(LevelError::MissingPlayer)?,
+            collectible,
+        })
+    }
+
+    pub fn width(&self) -> u16 {
+        self.width
+    }
+
+    pub fn height(&self) -> u16 {
+        self.height
+    }
+
+    /// The walls inside the surrounding wall.
+    pub fn walls(&self) -> Vec<Wall> {
+        self.walls
+            .iter()
+            .map(|position| Wall::new(position.x, position.y))
+            .collect()
+    }
+
+    pub fn enemy_spawns(&self) -> &[Point2d<u16>] {
+        &self.enemy_spawns
+    }
+
+    pub fn player_start(&self) -> Point2d<u16> {
+        self.player_start
+    }
+
+    pub fn collectible(&self) -> Option<Point2d<u16>> {
+        self.collectible
+    }
+}
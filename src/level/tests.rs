@@ -0,0 +1,56 @@
+#![cfg(test)]
+
+use crate::point::Point2d;
+
+use super::{Level, LevelError};
+
+#[test]
+fn test_level_parses_grid() {
+    let level = Level::parse(
+        "#######\n\
+         #P  *E#\n\
+         # ##  \n\
+         #E    #\n\
+         #######\n",
+    )
+    .unwrap();
+
+    assert_eq!((level.width(), level.height()), (7, 5));
+    assert_eq!(level.player_start(), Point2d::new(1, 1));
+    assert_eq!(level.collectible(), Some(Point2d::new(4, 1)));
+    assert_eq!(
+        level.enemy_spawns(),
+        &[Point2d::new(5, 1), Point2d::new(1, 3)]
+    );
+    // the surrounding wall is added by the game itself
+    assert_eq!(level.walls().len(), 2);
+}
+
+#[test]
+fn test_shipped_levels_are_valid() {
+    let level = Level::load("levels/arena.txt").unwrap();
+    assert_eq!(level.enemy_spawns().len(), 4);
+}
+
+#[test]
+fn test_level_reports_line_and_column() {
+    let error = Level::parse("#####\n#P x#\n#####").unwrap_err();
+    assert_eq!(
+        error,
+        LevelError::UnexpectedCharacter {
+            line: 2,
+            column: 4,
+            character: 'x'
+        }
+    );
+    assert!(error.to_string().starts_with("line 2, column 4: "));
+
+    let error = Level::parse("#####\n#P P#\n#####").unwrap_err();
+    assert_eq!(error, LevelError::DuplicatePlayer { line: 2, column: 4 });
+
+    let error = Level::parse("##E##\n#P  #\n#####").unwrap_err();
+    assert!(matches!(error, LevelError::OnTheBorder { line: 1, column: 3, .. }));
+
+    assert_eq!(Level::parse("####\n#  #\n####"), Err(LevelError::MissingPlayer));
+    assert_eq!(Level::parse("#P#"), Err(LevelError::TooSmall));
+}
@@ -1,11 +1,18 @@
+pub mod cli;
 pub mod game;
+pub mod input;
+pub mod level;
+pub mod map;
+pub mod point;
+pub mod scores;
+pub mod ui;
 pub mod unit;
 
-mod input;
+mod collision;
 mod hud;
 mod macros;
-mod point;
+mod pathfinding;
 mod traits;
-mod ui;
+mod visibility;
 
-pub mod common;
\ No newline at end of file
+pub mod common;
@@ -1,17 +1,83 @@
-use application::{game, unit::Enemy};
+use application::{
+    cli::Cli,
+    game::{Game, GameBuilder, Replay, RunOutcome},
+    ui::Renderer,
+};
+use crossterm::terminal;
+
+fn game_builder(cli: &Cli) -> GameBuilder {
+    match cli.game_builder() {
+        Ok(builder) => builder,
+        Err(error) => {
+            eprintln!("error: {}", error);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Builds game number `number` of the session from the options and starts
+/// it with `start`, drawing on `renderer` if it is given.
+fn new_game(
+    cli: &Cli,
+    number: usize,
+    start: fn(&mut Game) -> RunOutcome,
+    renderer: Option<Box<dyn Renderer>>,
+) -> (RunOutcome, Game) {
+    let mut builder = game_builder(cli);
+    if let Some(renderer) = renderer {
+        builder = builder.renderer(renderer);
+    }
+    let mut game = builder.build();
+    let outcome = start(&mut game);
+    if let Some(path) = cli.record_path(number) {
+        game.replay().save(&path).expect("failed to save the replay");
+    }
+    (outcome, game)
+}
 
 fn main() {
-    let mut game = game::Game::builder()
-        .n_random_walls(30)
-        .height(40)
-        .player_starting_health(10)
-        .player_starting_speed(2.0)
-        .enemies(
-            (1..10)
-                .map(|i| Enemy::with_speed(i as f64 * 0.1))
-                .collect(),
-        )
-        .update_interval(std::time::Duration::from_millis(280))
-        .build();
-    game.run();
+    let cli = Cli::parse_and_validate();
+
+    if let Some(path) = &cli.replay {
+        let replay = Replay::load(path).expect("failed to load the replay");
+        game_builder(&cli)
+            .seed(replay.seed())
+            .build()
+            .run_replay(&replay);
+        return;
+    }
+
+    let (mut outcome, mut game) = match &cli.resume {
+        Some(path) => {
+            let mut game = Game::load(path).expect("failed to load the saved game");
+            let settings = cli
+                .key_bindings(game.n_players())
+                .and_then(|bindings| Ok((bindings, cli.theme()?)));
+            match settings {
+                Ok((bindings, theme)) => {
+                    game.set_key_bindings(bindings);
+                    game.set_theme(theme);
+                }
+                Err(error) => {
+                    eprintln!("error: {}", error);
+                    std::process::exit(1);
+                }
+            }
+            if let Some(path) = cli.high_scores_path() {
+                game.set_high_scores_path(&path);
+            }
+            if let Ok((columns, rows)) = terminal::size() {
+                game.set_screen_size(columns, rows);
+            }
+            (game.resume(), game)
+        }
+        None => new_game(&cli, 0, Game::run, None),
+    };
+    // the next game takes the terminal over, which is only restored once
+    // the last game is dropped
+    let mut number = 0;
+    while outcome == RunOutcome::Restart {
+        number += 1;
+        (outcome, game) = new_game(&cli, number, Game::play, Some(game.into_renderer()));
+    }
 }
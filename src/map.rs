@@ -0,0 +1,153 @@
+mod caves;
+mod maze;
+mod rooms;
+mod tests;
+
+pub use caves::Caves;
+pub use maze::Maze;
+pub use rooms::Rooms;
+
+use std::collections::VecDeque;
+
+use rand::RngCore;
+
+use crate::{point::Point2d, unit::Wall};
+
+/// A grid of tiles that are either a wall or open.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TileMap {
+    width: u16,
+    height: u16,
+    walls: Vec<bool>,
+}
+
+impl TileMap {
+    /// A map where every tile is a wall if `filled`, or open otherwise.
+    pub fn new(width: u16, height: u16, filled: bool) -> Self {
+        Self {
+            width,
+            height,
+            walls: vec![filled; width as usize * height as usize],
+        }
+    }
+
+    pub fn width(&self) -> u16 {
+        self.width
+    }
+
+    pub fn height(&self) -> u16 {
+        self.height
+    }
+
+    fn index(&self, position: Point2d<u16>) -> Option<usize> {
+        if position.x < self.width && position.y < self.height {
+            Some(position.y as usize * self.width as usize + position.x as usize)
+        } else {
+            None
+        }
+    }
+
+    pub fn is_border(&self, position: Point2d<u16>) -> bool {
+        position.x == 0
+            || position.y == 0
+            || position.x + 1 >= self.width
+            || position.y + 1 >= self.height
+    }
+
+    /// Tiles outside of the map count as walls.
+    pub fn is_wall(&self, position: Point2d<u16>) -> bool {
+        self.index(position).is_none_or(|index| self.walls[index])
+    }
+
+    pub fn set_wall(&mut self, position: Point2d<u16>, wall: bool) {
+        if let Some(index) = self.index(position) {
+            self.walls[index] = wall;
+        }
+    }
+
+    /// The up to four tiles next to `position` that are inside the map.
+    pub fn neighbours(&self, position: Point2d<u16>) -> impl Iterator<Item = Point2d<u16>> + '_ {
+        let Point2d { x, y } = position;
+        [
+            x.checked_sub(1).map(|x| Point2d::new(x, y)),
+            y.checked_sub(1).map(|y| Point2d::new(x, y)),
+            Some(Point2d::new(x + 1, y)),
+            Some(Point2d::new(x, y + 1)),
+        ]
+        .into_iter()
+        .flatten()
+        .filter(|neighbour| self.index(*neighbour).is_some())
+    }
+
+    /// Turns every open tile that can not be reached from `start` into a
+    /// wall.
+    pub fn fill_unreachable(&mut self, start: Point2d<u16>) {
+        let mut reachable = vec![false; self.walls.len()];
+        let mut queue = VecDeque::new();
+        if let Some(index) = self.index(start).filter(|index| !self.walls[*index]) {
+            reachable[index] = true;
+            queue.push_back(start);
+        }
+
+        while let Some(position) = queue.pop_front() {
+            for neighbour in self.neighbours(position) {
+                let index = self.index(neighbour).unwrap();
+                if !self.walls[index] && !reachable[index] {
+                    reachable[index] = true;
+                    queue.push_back(neighbour);
+                }
+            }
+        }
+
+        self.walls
+            .iter_mut()
+            .zip(reachable)
+            .for_each(|(wall, reachable)| *wall |= !reachable);
+    }
+
+    /// The walls that are not on the border, which the game adds itself.
+    pub fn inner_walls(&self) -> Vec<Wall> {
+        (1..self.height.saturating_sub(1))
+            .flat_map(|y| (1..self.width.saturating_sub(1)).map(move |x| Point2d::new(x, y)))
+            .filter(|position| self.is_wall(*position))
+            .map(|position| Wall::new(position.x, position.y))
+            .collect()
+    }
+}
+
+/// Generates the walls of a map.
+pub trait MapGenerator {
+    /// Lays out a `width` x `height` map. The tiles on the border and the
+    /// ones that are not reachable from `start` are turned into walls
+    /// afterwards, so implementations do not need to care about them.
+    fn generate(
+        &self,
+        width: u16,
+        height: u16,
+        start: Point2d<u16>,
+        rng: &mut dyn RngCore,
+    ) -> TileMap;
+
+    /// A map whose border is walled and whose open tiles, including
+    /// `start`, are all reachable from `start`.
+    fn generate_connected(
+        &self,
+        width: u16,
+        height: u16,
+        start: Point2d<u16>,
+        rng: &mut dyn RngCore,
+    ) -> TileMap {
+        let mut map = self.generate(width, height, start, rng);
+        for y in 0..height {
+            for x in 0..width {
+                let position = Point2d::new(x, y);
+                if map.is_border(position) {
+                    map.set_wall(position, true);
+                }
+            }
+        }
+        map.set_wall(start, false);
+        map.fill_unreachable(start);
+        map
+    }
+}
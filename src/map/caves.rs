@@ -0,0 +1,80 @@
+use rand::{Rng, RngCore};
+
+use crate::point::Point2d;
+
+use super::{MapGenerator, TileMap};
+
+/// Organic caves made by a cellular automaton: the map starts as noise and
+/// every step turns a tile into a wall if most of its neighbours are walls.
+#[derive(Debug, Clone, Copy)]
+pub struct Caves {
+    /// Chance of a tile being a wall before smoothing.
+    pub fill_probability: f64,
+    pub smoothing_steps: u32,
+}
+
+impl Default for Caves {
+    fn default() -> Self {
+        Self {
+            fill_probability: 0.45,
+            smoothing_steps: 4,
+        }
+    }
+}
+
+fn count_neighbouring_walls(map: &TileMap, position: Point2d<u16>) -> usize {
+    (-1..=1)
+        .flat_map(|dy| (-1..=1).map(move |dx| (dx, dy)))
+        .filter(|offset| *offset != (0, 0))
+        .filter(|(dx, dy)| {
+            match (
+                position.x.checked_add_signed(*dx),
+                position.y.checked_add_signed(*dy),
+            ) {
+                (Some(x), Some(y)) => map.is_wall(Point2d::new(x, y)),
+                _ => true,
+            }
+        })
+        .count()
+}
+
+impl MapGenerator for Caves {
+    fn generate(
+        &self,
+        width: u16,
+        height: u16,
+        start: Point2d<u16>,
+        rng: &mut dyn RngCore,
+    ) -> TileMap {
+        let mut map = TileMap::new(width, height, true);
+        for y in 1..height.saturating_sub(1) {
+            for x in 1..width.saturating_sub(1) {
+                let wall = rng.gen_bool(self.fill_probability);
+                map.set_wall(Point2d::new(x, y), wall);
+            }
+        }
+
+        for _ in 0..self.smoothing_steps {
+            let mut smoothed = map.clone();
+            for y in 1..height.saturating_sub(1) {
+                for x in 1..width.saturating_sub(1) {
+                    let position = Point2d::new(x, y);
+                    match count_neighbouring_walls(&map, position) {
+                        0..=3 => smoothed.set_wall(position, false),
+                        5.. => smoothed.set_wall(position, true),
+                        _ => {}
+                    }
+                }
+            }
+            map = smoothed;
+        }
+
+        // give the player some room to start in
+        for y in start.y.saturating_sub(1)..=start.y.saturating_add(1) {
+            for x in start.x.saturating_sub(1)..=start.x.saturating_add(1) {
+                map.set_wall(Point2d::new(x, y), false);
+            }
+        }
+        map
+    }
+}
@@ -0,0 +1,68 @@
+use rand::{seq::SliceRandom, RngCore};
+
+use crate::point::Point2d;
+
+use super::{MapGenerator, TileMap};
+
+/// A perfect maze carved by a recursive backtracker. The cells of the maze
+/// lie on odd coordinates and the tiles between two cells are either a wall
+/// or a passage.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Maze;
+
+fn to_cell(coordinate: u16) -> u16 {
+    if coordinate % 2 == 1 {
+        coordinate
+    } else {
+        coordinate.saturating_sub(1).max(1)
+    }
+}
+
+impl MapGenerator for Maze {
+    fn generate(
+        &self,
+        width: u16,
+        height: u16,
+        start: Point2d<u16>,
+        rng: &mut dyn RngCore,
+    ) -> TileMap {
+        let mut map = TileMap::new(width, height, true);
+        let first = Point2d::new(to_cell(start.x), to_cell(start.y));
+        if map.is_border(first) {
+            return map;
+        }
+
+        // connect the start to the cell it lies next to
+        map.set_wall(start, false);
+        map.set_wall(Point2d::new(first.x, start.y), false);
+        map.set_wall(first, false);
+
+        // the backtracking is done with an explicit stack, so large maps do
+        // not overflow the call stack
+        let mut stack = vec![first];
+        while let Some(&cell) = stack.last() {
+            let unvisited: Vec<Point2d<u16>> = [(-2, 0), (2, 0), (0, -2), (0, 2)]
+                .into_iter()
+                .filter_map(|(dx, dy)| {
+                    let x = cell.x.checked_add_signed(dx)?;
+                    let y = cell.y.checked_add_signed(dy)?;
+                    Some(Point2d::new(x, y))
+                })
+                .filter(|next| !map.is_border(*next) && map.is_wall(*next))
+                .collect();
+
+            match unvisited.choose(rng) {
+                Some(&next) => {
+                    let between = Point2d::new((cell.x + next.x) / 2, (cell.y + next.y) / 2);
+                    map.set_wall(between, false);
+                    map.set_wall(next, false);
+                    stack.push(next);
+                }
+                None => {
+                    stack.pop();
+                }
+            }
+        }
+        map
+    }
+}
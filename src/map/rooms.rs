@@ -0,0 +1,109 @@
+use rand::{Rng, RngCore};
+
+use crate::point::Point2d;
+
+use super::{MapGenerator, TileMap};
+
+/// Rectangular rooms that do not overlap, each connected to the previous
+/// one by an L-shaped corridor. The first room is connected to the start.
+#[derive(Debug, Clone, Copy)]
+pub struct Rooms {
+    pub max_rooms: u32,
+    pub min_size: u16,
+    pub max_size: u16,
+}
+
+impl Default for Rooms {
+    fn default() -> Self {
+        Self {
+            max_rooms: 12,
+            min_size: 3,
+            max_size: 8,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Room {
+    x: u16,
+    y: u16,
+    width: u16,
+    height: u16,
+}
+
+impl Room {
+    fn center(&self) -> Point2d<u16> {
+        Point2d::new(self.x + self.width / 2, self.y + self.height / 2)
+    }
+
+    /// Whether the rooms overlap or touch each other.
+    fn touches(&self, other: &Room) -> bool {
+        self.x <= other.x + other.width
+            && other.x <= self.x + self.width
+            && self.y <= other.y + other.height
+            && other.y <= self.y + self.height
+    }
+}
+
+fn carve_corridor(map: &mut TileMap, from: Point2d<u16>, to: Point2d<u16>, horizontal_first: bool) {
+    let corner = if horizontal_first {
+        Point2d::new(to.x, from.y)
+    } else {
+        Point2d::new(from.x, to.y)
+    };
+    for (a, b) in [(from, corner), (corner, to)] {
+        for y in a.y.min(b.y)..=a.y.max(b.y) {
+            for x in a.x.min(b.x)..=a.x.max(b.x) {
+                map.set_wall(Point2d::new(x, y), false);
+            }
+        }
+    }
+}
+
+impl MapGenerator for Rooms {
+    fn generate(
+        &self,
+        width: u16,
+        height: u16,
+        start: Point2d<u16>,
+        rng: &mut dyn RngCore,
+    ) -> TileMap {
+        let mut map = TileMap::new(width, height, true);
+        let inner_width = width.saturating_sub(2);
+        let inner_height = height.saturating_sub(2);
+        let min_size = self.min_size.max(1);
+        let mut previous = start;
+        let mut rooms: Vec<Room> = vec![];
+
+        for _ in 0..self.max_rooms * 4 {
+            if rooms.len() as u32 >= self.max_rooms
+                || inner_width < min_size
+                || inner_height < min_size
+            {
+                break;
+            }
+            let room_width = rng.gen_range(min_size..=self.max_size.max(min_size).min(inner_width));
+            let room_height =
+                rng.gen_range(min_size..=self.max_size.max(min_size).min(inner_height));
+            let room = Room {
+                x: rng.gen_range(1..=inner_width - room_width + 1),
+                y: rng.gen_range(1..=inner_height - room_height + 1),
+                width: room_width,
+                height: room_height,
+            };
+            if rooms.iter().any(|other| other.touches(&room)) {
+                continue;
+            }
+
+            for y in room.y..room.y + room.height {
+                for x in room.x..room.x + room.width {
+                    map.set_wall(Point2d::new(x, y), false);
+                }
+            }
+            carve_corridor(&mut map, previous, room.center(), rng.gen());
+            previous = room.center();
+            rooms.push(room);
+        }
+        map
+    }
+}
@@ -0,0 +1,52 @@
+#![cfg(test)]
+
+use rand::SeedableRng;
+use rand_chacha::ChaCha12Rng;
+
+use crate::point::Point2d;
+
+use super::*;
+
+fn assert_connected(map: &TileMap, start: Point2d<u16>) {
+    assert!(!map.is_wall(start));
+
+    let mut reachable = map.clone();
+    reachable.fill_unreachable(start);
+    assert_eq!(&reachable, map, "some open tiles can not be reached");
+
+    for y in 0..map.height() {
+        for x in 0..map.width() {
+            let position = Point2d::new(x, y);
+            if map.is_border(position) {
+                assert!(map.is_wall(position));
+            }
+        }
+    }
+}
+
+#[test]
+fn test_generated_maps_are_connected() {
+    let generators: [&dyn MapGenerator; 3] = [&Maze, &Caves::default(), &Rooms::default()];
+    for generator in generators {
+        for seed in 0..20 {
+            let mut rng = ChaCha12Rng::seed_from_u64(seed);
+            let start = Point2d::new(seed as u16 % 7 + 1, 2);
+            let map = generator.generate_connected(41, 23, start, &mut rng);
+            assert_connected(&map, start);
+            // the map is neither empty nor solid
+            let walls = map.inner_walls().len();
+            assert!(walls > 0 && walls < 39 * 21, "{} inner walls", walls);
+        }
+    }
+}
+
+#[test]
+fn test_fill_unreachable() {
+    let mut map = TileMap::new(5, 5, false);
+    for y in 0..5 {
+        map.set_wall(Point2d::new(2, y), true);
+    }
+    map.fill_unreachable(Point2d::new(0, 0));
+    assert!(!map.is_wall(Point2d::new(1, 4)));
+    assert!(map.is_wall(Point2d::new(3, 0)));
+}
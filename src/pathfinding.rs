@@ -0,0 +1,108 @@
+mod tests;
+
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap},
+};
+
+use crate::point::Point2d;
+
+const STRAIGHT_COST: u32 = 10;
+const DIAGONAL_COST: u32 = 14;
+
+/// Octile distance, the exact cost between two tiles on an empty grid.
+fn heuristic(from: Point2d<u16>, to: Point2d<u16>) -> u32 {
+    let dx = from.x.abs_diff(to.x) as u32;
+    let dy = from.y.abs_diff(to.y) as u32;
+    STRAIGHT_COST * dx.max(dy) + (DIAGONAL_COST - STRAIGHT_COST) * dx.min(dy)
+}
+
+/// The tiles next to `position` that are not blocked, with the cost of
+/// stepping on them. Diagonal steps are only allowed when both tiles they
+/// cut past are free, so a path never squeezes between two walls.
+fn neighbours(
+    position: Point2d<u16>,
+    is_blocked: &impl Fn(Point2d<u16>) -> bool,
+) -> Vec<(Point2d<u16>, u32)> {
+    let step = |dx: i16, dy: i16| -> Option<Point2d<u16>> {
+        let x = position.x.checked_add_signed(dx)?;
+        let y = position.y.checked_add_signed(dy)?;
+        Some(Point2d::new(x, y)).filter(|next| !is_blocked(*next))
+    };
+
+    let mut neighbours = vec![];
+    for (dx, dy) in [(1, 0), (-1, 0), (0, 1), (0, -1)] {
+        if let Some(next) = step(dx, dy) {
+            neighbours.push((next, STRAIGHT_COST));
+        }
+    }
+    for (dx, dy) in [(1, 1), (1, -1), (-1, 1), (-1, -1)] {
+        if step(dx, 0).is_some() && step(0, dy).is_some() {
+            if let Some(next) = step(dx, dy) {
+                neighbours.push((next, DIAGONAL_COST));
+            }
+        }
+    }
+    neighbours
+}
+
+/// Finds the shortest path from `start` to `goal` with A*, moving in eight
+/// directions over the tiles for which `is_blocked` is false. The returned
+/// path excludes `start` and ends with `goal`. `is_blocked` has to be true
+/// outside of the playing area, otherwise the search may not terminate when
+/// there is no path.
+pub fn find_path(
+    start: Point2d<u16>,
+    goal: Point2d<u16>,
+    is_blocked: impl Fn(Point2d<u16>) -> bool,
+) -> Option<Vec<Point2d<u16>>> {
+    if is_blocked(goal) {
+        return None;
+    }
+    if start == goal {
+        return Some(vec![]);
+    }
+
+    let mut open = BinaryHeap::new();
+    let mut cost_so_far: HashMap<Point2d<u16>, u32> = HashMap::new();
+    let mut came_from: HashMap<Point2d<u16>, Point2d<u16>> = HashMap::new();
+
+    open.push(Reverse((heuristic(start, goal), 0, start.y, start.x)));
+    cost_so_far.insert(start, 0);
+
+    while let Some(Reverse((_, cost, y, x))) = open.pop() {
+        let position = Point2d::new(x, y);
+        if position == goal {
+            let mut path = vec![goal];
+            let mut current = goal;
+            while let Some(&previous) = came_from.get(&current) {
+                if previous == start {
+                    break;
+                }
+                path.push(previous);
+                current = previous;
+            }
+            path.reverse();
+            return Some(path);
+        }
+        if cost > cost_so_far[&position] {
+            // a cheaper way to this tile was found after it was queued
+            continue;
+        }
+
+        for (next, step_cost) in neighbours(position, &is_blocked) {
+            let next_cost = cost + step_cost;
+            if cost_so_far.get(&next).is_none_or(|known| next_cost < *known) {
+                cost_so_far.insert(next, next_cost);
+                came_from.insert(next, position);
+                open.push(Reverse((
+                    next_cost + heuristic(next, goal),
+                    next_cost,
+                    next.y,
+                    next.x,
+                )));
+            }
+        }
+    }
+    None
+}
@@ -0,0 +1,58 @@
+#![cfg(test)]
+
+use std::collections::HashSet;
+
+use crate::point::Point2d;
+
+use super::find_path;
+
+/// Parses a grid where `#` is blocked. Everything outside of it is blocked.
+fn blocked(grid: &[&str]) -> impl Fn(Point2d<u16>) -> bool {
+    let open: HashSet<Point2d<u16>> = grid
+        .iter()
+        .enumerate()
+        .flat_map(|(y, row)| {
+            row.chars()
+                .enumerate()
+                .filter(|(_, tile)| *tile != '#')
+                .map(move |(x, _)| Point2d::new(x as u16, y as u16))
+        })
+        .collect();
+    move |position| !open.contains(&position)
+}
+
+#[test]
+fn test_path_goes_around_walls() {
+    let is_blocked = blocked(&[
+        "#######", //
+        "#  #  #", //
+        "#  #  #", //
+        "#     #", //
+        "#######", //
+    ]);
+    let path = find_path(Point2d::new(1, 1), Point2d::new(5, 1), &is_blocked).unwrap();
+
+    assert_eq!(path.last(), Some(&Point2d::new(5, 1)));
+    assert_eq!(path.len(), 6);
+    // the only way is through the bottom row, and the corners of the wall
+    // in the middle are never cut
+    assert!(path.contains(&Point2d::new(3, 3)));
+    let mut previous = Point2d::new(1, 1);
+    for tile in path {
+        assert!(!is_blocked(tile));
+        assert!(!is_blocked(Point2d::new(tile.x, previous.y)));
+        assert!(!is_blocked(Point2d::new(previous.x, tile.y)));
+        previous = tile;
+    }
+}
+
+#[test]
+fn test_no_path_to_enclosed_tiles() {
+    let is_blocked = blocked(&[
+        "#####", //
+        "# # #", //
+        "#####", //
+    ]);
+    assert_eq!(find_path(Point2d::new(1, 1), Point2d::new(3, 1), &is_blocked), None);
+    assert_eq!(find_path(Point2d::new(1, 1), Point2d::new(1, 1), &is_blocked), Some(vec![]));
+}
@@ -1,4 +1,7 @@
-use std::ops::{Add, AddAssign, Mul, Sub};
+use std::{
+    hash::{Hash, Hasher},
+    ops::{Add, AddAssign, Mul, Sub},
+};
 
 use num::{self, traits::NumAssign, Float, Zero};
 
@@ -121,12 +124,34 @@ impl Point2d<f64> {
     }
 }
 
+impl Point2d<u16> {
+    pub fn to_f64(self) -> Point2d<f64> {
+        Point2d::new(self.x.into(), self.y.into())
+    }
+}
+
 impl PartialEq for Point2d<u16> {
     fn eq(&self, other: &Self) -> bool {
         self.x == other.x && self.y == other.y
     }
 }
 
+impl Eq for Point2d<u16> {}
+
+impl Hash for Point2d<u16> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.x.hash(state);
+        self.y.hash(state);
+    }
+}
+
+impl PartialEq for Point2d<i32> {
+    fn eq(&self, other: &Self) -> bool {
+        self.x == other.x && self.y == other.y
+    }
+}
+
+impl Eq for Point2d<i32> {}
 
 impl PartialEq for Point2d<f64> {
     fn eq(&self, other: &Self) -> bool {
@@ -0,0 +1,98 @@
+mod tests;
+
+use std::{error::Error, fs, path::PathBuf, time::Duration};
+
+use serde::{Deserialize, Serialize};
+
+use crate::common::JsonIo;
+
+/// How many scores the table keeps.
+pub const CAPACITY: usize = 10;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HighScore {
+    pub name: String,
+    pub score: u32,
+    /// How long the game was played for.
+    pub duration: Duration,
+    pub seed: u64,
+    /// The map the game was played on, e.g. `maze` or the path of a level.
+    pub map: String,
+}
+
+/// The best scores, best first.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HighScores {
+    entries: Vec<HighScore>,
+}
+
+impl HighScores {
+    /// Where the table is kept unless another file is given, in the data
+    /// directory of the user.
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::data_dir().map(|dir| dir.join("hide-n-seek").join("high_scores.json"))
+    }
+
+    /// Loads the table from `path`. A missing file is an empty table. A file
+    /// that is not a table is moved aside to `<path>.corrupt`, so that it is
+    /// not overwritten by the next save, and the table starts empty.
+    pub fn load(path: &str) -> Self {
+        if !fs::exists(path).unwrap_or(false) {
+            return Self::default();
+        }
+        match JsonIo::new(path).read_json::<Self>() {
+            Ok(mut high_scores) => {
+                // the file may have been edited by hand
+                high_scores
+                    .entries
+                    .sort_by_key(|entry| std::cmp::Reverse(entry.score));
+                high_scores.entries.truncate(CAPACITY);
+                high_scores
+            }
+            Err(_) => {
+                let _ = fs::rename(path, format!("{}.corrupt", path));
+                Self::default()
+            }
+        }
+    }
+
+    /// Saves the table to `path`, creating its directory if needed. The
+    /// table is written to a temporary file first, so a failed save never
+    /// leaves a half written table behind.
+    pub fn save(&self, path: &str) -> Result<(), Box<dyn Error>> {
+        if let Some(dir) = PathBuf::from(path).parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let temporary = format!("{}.tmp", path);
+        JsonIo::new(&temporary).write_json(self)?;
+        fs::rename(&temporary, path)?;
+        Ok(())
+    }
+
+    pub fn entries(&self) -> &[HighScore] {
+        &self.entries
+    }
+
+    /// Whether `score` would make it onto the table.
+    pub fn qualifies(&self, score: u32) -> bool {
+        score > 0
+            && (self.entries.len() < CAPACITY
+                || self.entries.last().is_some_and(|last| score > last.score))
+    }
+
+    /// Adds `entry` below the entries with the same or a better score and
+    /// returns its place, starting from 0, if it made it onto the table.
+    pub fn insert(&mut self, entry: HighScore) -> Option<usize> {
+        let place = self
+            .entries
+            .iter()
+            .position(|other| entry.score > other.score)
+            .unwrap_or(self.entries.len());
+        if place >= CAPACITY {
+            return None;
+        }
+        self.entries.insert(place, entry);
+        self.entries.truncate(CAPACITY);
+        Some(place)
+    }
+}
@@ -0,0 +1,58 @@
+#![cfg(test)]
+
+use std::{fs, time::Duration};
+
+use super::*;
+
+fn entry(name: &str, score: u32) -> HighScore {
+    HighScore {
+        name: name.to_string(),
+        score,
+        duration: Duration::from_secs(60),
+        seed: 7,
+        map: "maze".to_string(),
+    }
+}
+
+#[test]
+fn test_table_keeps_the_best_scores_in_order() {
+    let mut high_scores = HighScores::default();
+    assert!(!high_scores.qualifies(0));
+
+    for score in 1..=CAPACITY as u32 {
+        assert!(high_scores.qualifies(score));
+        high_scores.insert(entry("a", score));
+    }
+    assert_eq!(high_scores.entries()[0].score, CAPACITY as u32);
+    assert!(!high_scores.qualifies(1));
+    assert_eq!(high_scores.insert(entry("b", 1)), None);
+
+    // a tie ranks below the score that was there first
+    assert_eq!(high_scores.insert(entry("c", 5)), Some(6));
+    assert_eq!(high_scores.entries().len(), CAPACITY);
+    assert_eq!(high_scores.entries()[5].name, "a");
+    assert_eq!(high_scores.entries().last().unwrap().score, 2);
+}
+
+#[test]
+fn test_missing_and_corrupt_files_start_an_empty_table() {
+    let dir = std::env::temp_dir().join("hide-n-seek-scores");
+    let _ = fs::remove_dir_all(&dir);
+    let path = dir.join("high_scores.json");
+    let path = path.to_str().unwrap();
+
+    assert_eq!(HighScores::load(path), HighScores::default());
+
+    let mut high_scores = HighScores::default();
+    high_scores.insert(entry("a", 3));
+    high_scores.save(path).unwrap();
+    assert_eq!(HighScores::load(path), high_scores);
+
+    fs::write(path, "{ not json").unwrap();
+    assert_eq!(HighScores::load(path), HighScores::default());
+    assert!(!fs::exists(path).unwrap());
+    assert_eq!(
+        fs::read_to_string(format!("{}.corrupt", path)).unwrap(),
+        "{ not json"
+    );
+}
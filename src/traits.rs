@@ -11,7 +11,7 @@ use crate::{game::Game, point::Point2d};
 pub trait Position<T: NumAssign + Copy> {
     fn position(&self) -> Point2d<T>;
     fn set_position(&mut self, position: Point2d<T>);
-    fn set_rand_position(&mut self, rng: &mut Box<dyn RngCore>, x_range: Range<T>, y_range: Range<T>)
+    fn set_rand_position(&mut self, rng: &mut dyn RngCore, x_range: Range<T>, y_range: Range<T>)
     where
         T: PartialOrd + SampleUniform,
         Standard: Distribution<T>,
@@ -0,0 +1,145 @@
+mod tests;
+
+use crate::point::Point2d;
+
+/// Which part of the world is shown on the screen, for worlds that do not
+/// fit on it.
+///
+/// The camera only moves once the followed position leaves the dead zone, a
+/// box in the middle of the view, so small moves do not scroll the whole
+/// map. Unless clamping is turned off, the camera stops at the edges of the
+/// world instead of showing what is beyond them.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Camera {
+    /// Number of columns and rows the world is shown in.
+    size: Point2d<u16>,
+    dead_zone: Point2d<u16>,
+    clamped: bool,
+    /// The tile of the world shown in the top left cell.
+    origin: Point2d<i32>,
+}
+
+impl Default for Camera {
+    fn default() -> Self {
+        Self::new(0, 0)
+    }
+}
+
+impl Camera {
+    pub fn new(width: u16, height: u16) -> Self {
+        Self {
+            size: Point2d::new(width, height),
+            dead_zone: Point2d::new(16, 8),
+            clamped: true,
+            origin: Point2d::new(0, 0),
+        }
+    }
+
+    /// How far the followed position can move in the middle of the view
+    /// before the camera moves along.
+    pub fn with_dead_zone(mut self, width: u16, height: u16) -> Self {
+        self.dead_zone = Point2d::new(width, height);
+        self
+    }
+
+    /// Whether the camera stops at the edges of the world.
+    pub fn with_clamping(mut self, clamped: bool) -> Self {
+        self.clamped = clamped;
+        self
+    }
+
+    pub fn width(&self) -> u16 {
+        self.size.x
+    }
+
+    pub fn height(&self) -> u16 {
+        self.size.y
+    }
+
+    pub fn origin(&self) -> Point2d<i32> {
+        self.origin
+    }
+
+    pub fn resize(&mut self, width: u16, height: u16) {
+        self.size = Point2d::new(width, height);
+    }
+
+    /// Moves the camera so that `target` is in the middle of the view.
+    pub fn center_on(&mut self, target: Point2d<f64>, world: Point2d<u16>) {
+        let target = target.round();
+        self.origin = Point2d::new(
+            target.x as i32 - self.size.x as i32 / 2,
+            target.y as i32 - self.size.y as i32 / 2,
+        );
+        self.clamp(world);
+    }
+
+    /// Moves the camera just as far as needed to keep `target` in the dead
+    /// zone.
+    pub fn follow(&mut self, target: Point2d<f64>, world: Point2d<u16>) {
+        let target = target.round();
+        self.origin = Point2d::new(
+            follow_axis(
+                self.origin.x,
+                self.size.x,
+                self.dead_zone.x,
+                target.x as i32,
+            ),
+            follow_axis(
+                self.origin.y,
+                self.size.y,
+                self.dead_zone.y,
+                target.y as i32,
+            ),
+        );
+        self.clamp(world);
+    }
+
+    fn clamp(&mut self, world: Point2d<u16>) {
+        if self.clamped {
+            self.origin = Point2d::new(
+                clamp_axis(self.origin.x, self.size.x, world.x),
+                clamp_axis(self.origin.y, self.size.y, world.y),
+            );
+        }
+    }
+
+    /// The tiles of the world in view, row by row.
+    pub fn visible_tiles(&self) -> impl Iterator<Item = Point2d<u16>> {
+        let origin = self.origin;
+        let columns = (0..self.size.x as i32).map(move |x| x + origin.x);
+        let rows = (0..self.size.y as i32).map(move |y| y + origin.y);
+        rows.flat_map(move |y| columns.clone().map(move |x| (x, y)))
+            .filter_map(|(x, y)| Some(Point2d::new(x.try_into().ok()?, y.try_into().ok()?)))
+    }
+
+    /// The cell `position` of the world is shown in, none if it is out of
+    /// view.
+    pub fn to_screen(&self, position: Point2d<f64>) -> Option<Point2d<u16>> {
+        let position = position.round();
+        let x = position.x as i32 - self.origin.x;
+        let y = position.y as i32 - self.origin.y;
+        let in_view = (0..self.size.x as i32).contains(&x) && (0..self.size.y as i32).contains(&y);
+        in_view.then(|| Point2d::new(x as u16, y as u16))
+    }
+}
+
+/// The origin on one axis that keeps `target` within the dead zone.
+fn follow_axis(origin: i32, size: u16, dead_zone: u16, target: i32) -> i32 {
+    let dead_zone = dead_zone.min(size).max(1) as i32;
+    let first = origin + (size as i32 - dead_zone) / 2;
+    let last = first + dead_zone - 1;
+    if target < first {
+        origin - (first - target)
+    } else if target > last {
+        origin + (target - last)
+    } else {
+        origin
+    }
+}
+
+/// The origin on one axis that shows no tiles beyond the edges of a world
+/// `world` tiles long. A world shorter than the view starts at its top left.
+fn clamp_axis(origin: i32, size: u16, world: u16) -> i32 {
+    origin.min(world as i32 - size as i32).max(0)
+}
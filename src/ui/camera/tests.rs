@@ -0,0 +1,54 @@
+#![cfg(test)]
+
+use super::*;
+
+const WORLD: Point2d<u16> = Point2d { x: 500, y: 500 };
+
+#[test]
+fn test_camera_moves_once_the_target_leaves_the_dead_zone() {
+    let mut camera = Camera::new(40, 20).with_dead_zone(10, 4);
+    camera.center_on(Point2d::new(100.0, 100.0), WORLD);
+    assert_eq!(camera.origin(), Point2d::new(80, 90));
+
+    // the dead zone spans the columns 95 to 104 and the rows 98 to 101
+    camera.follow(Point2d::new(104.0, 98.0), WORLD);
+    assert_eq!(camera.origin(), Point2d::new(80, 90));
+
+    camera.follow(Point2d::new(107.0, 96.0), WORLD);
+    assert_eq!(camera.origin(), Point2d::new(83, 88));
+}
+
+#[test]
+fn test_camera_stops_at_the_edges_of_the_world() {
+    let mut camera = Camera::new(40, 20);
+    camera.center_on(Point2d::new(2.0, 498.0), WORLD);
+    assert_eq!(camera.origin(), Point2d::new(0, 480));
+
+    let mut camera = camera.with_clamping(false);
+    camera.center_on(Point2d::new(2.0, 498.0), WORLD);
+    assert_eq!(camera.origin(), Point2d::new(-18, 488));
+
+    // a world smaller than the view is shown from its top left
+    let mut camera = Camera::new(40, 20);
+    camera.center_on(Point2d::new(10.0, 5.0), Point2d::new(20, 10));
+    assert_eq!(camera.origin(), Point2d::new(0, 0));
+}
+
+#[test]
+fn test_only_what_is_in_view_is_shown() {
+    let mut camera = Camera::new(4, 3).with_clamping(false);
+    camera.center_on(Point2d::new(1.0, 0.0), WORLD);
+    assert_eq!(camera.origin(), Point2d::new(-1, -1));
+
+    assert_eq!(
+        camera.to_screen(Point2d::new(0.6, 1.2)),
+        Some(Point2d::new(2, 2))
+    );
+    assert_eq!(camera.to_screen(Point2d::new(3.0, 0.0)), None);
+    assert_eq!(camera.to_screen(Point2d::new(0.0, 2.0)), None);
+
+    let tiles: Vec<Point2d<u16>> = camera.visible_tiles().collect();
+    assert_eq!(tiles.len(), 6);
+    assert_eq!(tiles[0], Point2d::new(0, 0));
+    assert_eq!(tiles[5], Point2d::new(2, 1));
+}
@@ -1,47 +1,99 @@
 use crate::{
     hud::Hud,
-    impl_display,
+    point::Point2d,
     traits::Position,
     unit::{Collectible, Enemy, Player, Wall},
 };
-use std::{fmt::Display, io::Write};
 
-use crossterm::style::Stylize;
+use crossterm::style::{StyledContent, Stylize};
 use num::{traits::NumAssign, NumCast};
 
-pub trait Draw<T: NumAssign + Copy + NumCast>: Position<T> + Display {
-    fn draw(&self, stdout: &mut impl Write) {
+use super::{Camera, Renderer, Theme};
+
+pub trait Draw<T: NumAssign + Copy + NumCast>: Position<T> {
+    fn styled(&self, theme: &Theme) -> StyledContent<String>;
+
+    fn draw(&self, theme: &Theme, camera: &Camera, renderer: &mut dyn Renderer) {
         let position = self.position();
-        crossterm::queue!(
-            stdout,
-            crossterm::cursor::MoveTo(
+        self.draw_at(
+            Point2d::new(
                 position
                     .x
                     .to_f64()
-                    .expect("could not convert position x to f64")
-                    .round() as u16,
+                    .expect("could not convert position x to f64"),
                 position
                     .y
                     .to_f64()
-                    .expect("could not convert position y to f64")
-                    .round() as u16,
+                    .expect("could not convert position y to f64"),
             ),
-            crossterm::style::Print(self)
-        )
-        .unwrap();
+            theme,
+            camera,
+            renderer,
+        );
+    }
+
+    /// Draws the unit at `position` instead of where it is, e.g. in between
+    /// two updates. Nothing is drawn when `camera` does not show `position`.
+    fn draw_at(
+        &self,
+        position: Point2d<f64>,
+        theme: &Theme,
+        camera: &Camera,
+        renderer: &mut dyn Renderer,
+    ) {
+        if let Some(cell) = camera.to_screen(position) {
+            renderer.print_at(cell, &self.styled(theme));
+        }
     }
 }
 
-impl Draw<f64> for Player {}
+impl Draw<f64> for Player {
+    fn styled(&self, theme: &Theme) -> StyledContent<String> {
+        let icon = theme.player(self.number(), self.facing()).styled();
+        if self.is_flashing() {
+            icon.reverse()
+        } else {
+            icon
+        }
+    }
+}
+
+impl Draw<u16> for Collectible {
+    fn styled(&self, theme: &Theme) -> StyledContent<String> {
+        theme.collectible(self.kind()).styled()
+    }
+}
 
-impl Draw<u16> for Collectible {}
-impl_display!(for Collectible: '❤'.red());
+impl Draw<u16> for Wall {
+    fn styled(&self, theme: &Theme) -> StyledContent<String> {
+        theme.wall.styled()
+    }
+}
 
-impl Draw<u16> for Wall {}
-impl_display!(for Wall: '▓'.magenta());
+impl Draw<f64> for Enemy {
+    fn styled(&self, theme: &Theme) -> StyledContent<String> {
+        theme.enemy.styled()
+    }
+}
 
-impl Draw<f64> for Enemy {}
-impl_display!(for Enemy: '⁂'.dark_green());
+impl Draw<u16> for Hud {
+    fn styled(&self, _theme: &Theme) -> StyledContent<String> {
+        self.text().stylize()
+    }
 
-impl Draw<u16> for Hud {}
-impl_display!(for Hud: method text);
+    /// Draws the line of each player below the one before, in the colour
+    /// of the player if there are several. The HUD stays below the view of
+    /// `camera` wherever it looks.
+    fn draw(&self, theme: &Theme, _camera: &Camera, renderer: &mut dyn Renderer) {
+        let lines = self.lines();
+        let numbered = lines.len() > 1;
+        for (number, line) in (0..).zip(lines) {
+            let position = self.position() + Point2d::new(0, number);
+            let line = match numbered {
+                true => line.with(theme.player_color(number.into())),
+                false => line.stylize(),
+            };
+            renderer.print_at(position, &line);
+        }
+    }
+}
@@ -0,0 +1,187 @@
+use crossterm::style::{Color, ContentStyle, StyledContent};
+
+use crate::point::Point2d;
+
+use super::Renderer;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cell {
+    pub glyph: char,
+    pub style: ContentStyle,
+}
+
+impl Default for Cell {
+    fn default() -> Self {
+        Self {
+            glyph: ' ',
+            style: ContentStyle::default(),
+        }
+    }
+}
+
+/// A `width` x `height` frame of cells. Everything printed outside of it is
+/// clipped.
+#[derive(Debug, Clone)]
+pub struct CellGrid {
+    width: u16,
+    height: u16,
+    cells: Vec<Cell>,
+}
+
+impl CellGrid {
+    pub fn new(width: u16, height: u16) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![Cell::default(); width as usize * height as usize],
+        }
+    }
+
+    fn index(&self, position: Point2d<u16>) -> Option<usize> {
+        if position.x < self.width && position.y < self.height {
+            Some(position.y as usize * self.width as usize + position.x as usize)
+        } else {
+            None
+        }
+    }
+
+    pub fn width(&self) -> u16 {
+        self.width
+    }
+
+    pub fn height(&self) -> u16 {
+        self.height
+    }
+
+    pub fn cell(&self, position: Point2d<u16>) -> Option<Cell> {
+        self.index(position).map(|index| self.cells[index])
+    }
+
+    /// The cells row by row, with their positions.
+    pub fn cells(&self) -> impl Iterator<Item = (Point2d<u16>, Cell)> + '_ {
+        let width = self.width.max(1) as usize;
+        self.cells.iter().enumerate().map(move |(index, cell)| {
+            let position = Point2d::new((index % width) as u16, (index / width) as u16);
+            (position, *cell)
+        })
+    }
+
+    pub fn set(&mut self, position: Point2d<u16>, cell: Cell) {
+        if let Some(index) = self.index(position) {
+            self.cells[index] = cell;
+        }
+    }
+
+    pub fn fill(&mut self, cell: Cell) {
+        self.cells.fill(cell);
+    }
+
+    pub fn copy_from(&mut self, other: &CellGrid) {
+        self.cells.copy_from_slice(&other.cells);
+    }
+
+    /// Prints `content` from `position` on to the right, one cell per
+    /// character.
+    pub fn print_at(&mut self, position: Point2d<u16>, content: &StyledContent<String>) {
+        for (offset, glyph) in content.content().chars().enumerate() {
+            let Some(x) = u16::try_from(offset)
+                .ok()
+                .and_then(|offset| position.x.checked_add(offset))
+            else {
+                break;
+            };
+            self.set(
+                Point2d::new(x, position.y),
+                Cell {
+                    glyph,
+                    style: *content.style(),
+                },
+            );
+        }
+    }
+}
+
+/// An in-memory renderer. Everything printed outside of `width` x `height`
+/// is clipped. The getters read the last presented frame.
+#[derive(Debug, Clone)]
+pub struct GridRenderer {
+    back: CellGrid,
+    front: CellGrid,
+    frames: usize,
+    background: Color,
+}
+
+impl GridRenderer {
+    pub fn new(width: u16, height: u16) -> Self {
+        let cells = CellGrid::new(width, height);
+        Self {
+            back: cells.clone(),
+            front: cells,
+            frames: 0,
+            background: Color::Black,
+        }
+    }
+
+    pub fn width(&self) -> u16 {
+        self.front.width()
+    }
+
+    pub fn height(&self) -> u16 {
+        self.front.height()
+    }
+
+    /// The colour behind the cells without a background of their own.
+    pub fn background(&self) -> Color {
+        self.background
+    }
+
+    /// Number of frames presented so far.
+    pub fn frames(&self) -> usize {
+        self.frames
+    }
+
+    pub fn cell(&self, position: Point2d<u16>) -> Option<Cell> {
+        self.front.cell(position)
+    }
+
+    pub fn glyph(&self, position: Point2d<u16>) -> Option<char> {
+        self.cell(position).map(|cell| cell.glyph)
+    }
+
+    pub fn row(&self, y: u16) -> String {
+        (0..self.width())
+            .filter_map(|x| self.glyph(Point2d::new(x, y)))
+            .collect()
+    }
+
+    pub fn rows(&self) -> Vec<String> {
+        (0..self.height()).map(|y| self.row(y)).collect()
+    }
+}
+
+impl Renderer for GridRenderer {
+    fn prepare(&mut self) {}
+
+    fn clear(&mut self) {
+        self.back.fill(Cell::default());
+    }
+
+    fn print_at(&mut self, position: Point2d<u16>, content: &StyledContent<String>) {
+        self.back.print_at(position, content);
+    }
+
+    fn present(&mut self) {
+        self.front.copy_from(&self.back);
+        self.frames += 1;
+    }
+
+    /// Keeps the size the grid was created with, like a terminal that can
+    /// not be resized.
+    fn resize(&mut self, _columns: u16, _rows: u16) {}
+
+    fn set_background(&mut self, color: Color) {
+        self.background = color;
+    }
+
+    fn restore(&mut self) {}
+}
@@ -1,49 +1,226 @@
+pub mod camera;
 pub mod draw;
+pub mod grid;
+pub mod screen;
+pub mod terminal;
+mod tests;
+pub mod theme;
 
-use std::io::{stdout, Stdout};
+pub use camera::Camera;
+pub use grid::{Cell, CellGrid, GridRenderer};
+pub use screen::Screen;
+pub use theme::{Look, Theme};
+
+use std::{
+    any::Any,
+    io::{stdout, Stdout, Write},
+};
 
 use crossterm::{
     cursor::{self, MoveTo},
+    event::{KeyboardEnhancementFlags, PushKeyboardEnhancementFlags},
     execute, queue,
-    style::{Color, ResetColor, SetBackgroundColor},
-    terminal::{disable_raw_mode, enable_raw_mode, Clear, ClearType},
+    style::{
+        Attribute, Color, ContentStyle, Print, SetAttribute, SetAttributes, SetBackgroundColor,
+        SetForegroundColor, StyledContent,
+    },
+    terminal::{Clear, ClearType},
 };
 
+use crate::point::Point2d;
+
+/// Something the game can be drawn on.
+///
+/// `UI` renders to the terminal, while `GridRenderer` keeps the frame in
+/// memory so the game can run (and be inspected) without a TTY.
+pub trait Renderer: Any {
+    /// Gets ready to draw a game, also one after another on the same
+    /// renderer.
+    fn prepare(&mut self);
+    fn clear(&mut self);
+    fn print_at(&mut self, position: Point2d<u16>, content: &StyledContent<String>);
+    /// Shows everything printed since the last `clear`.
+    fn present(&mut self);
+    /// Adapts to a screen that is now `columns` x `rows` cells.
+    fn resize(&mut self, columns: u16, rows: u16);
+    /// The colour behind the cells that have no background of their own.
+    fn set_background(&mut self, color: Color);
+    fn restore(&mut self);
+}
+
+/// Renders to the terminal, or to another `out` such as a `Vec<u8>`.
+///
+/// Once prepared, the terminal is restored when the renderer is dropped,
+/// even while panicking.
+///
+/// A frame is drawn into a grid of cells first. Presenting it only writes
+/// the cells that differ from what the terminal shows, moving the cursor
+/// and changing the colours only where needed, so the screen does not
+/// flicker on slow terminals or over SSH.
 #[derive(Debug)]
-pub struct UI {
-    stdout: Stdout,
+pub struct UI<W: Write = Stdout> {
+    out: W,
+    buffer: Vec<u8>,
+    /// The frame that is drawn.
+    frame: CellGrid,
+    /// What the terminal shows.
+    shown: CellGrid,
+    background: Color,
+    /// Number of bytes written for the last frame.
+    frame_bytes: usize,
+    /// Whether this renderer prepared the terminal and has to restore it.
+    prepared: bool,
 }
 
 impl UI {
     pub fn new() -> Self {
-        let stdout = stdout();
-        Self { stdout }
+        let (columns, rows) = crossterm::terminal::size().unwrap_or((80, 24));
+        Self::with_writer(stdout(), columns, rows)
     }
+}
 
-    pub fn prepare(&mut self) {
-        enable_raw_mode().unwrap();
-        execute!(
-            self.stdout,
-            SetBackgroundColor(Color::Black),
-            Clear(ClearType::All),
-            cursor::Hide,
+impl Default for UI {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<W: Write> UI<W> {
+    /// A renderer that writes the frames for a `columns` x `rows` terminal
+    /// to `out`.
+    pub fn with_writer(out: W, columns: u16, rows: u16) -> Self {
+        Self {
+            out,
+            buffer: Vec::new(),
+            frame: CellGrid::new(columns, rows),
+            shown: CellGrid::new(columns, rows),
+            background: Color::Black,
+            frame_bytes: 0,
+            prepared: false,
+        }
+    }
+
+    /// Number of bytes written for the last frame.
+    pub fn frame_bytes(&self) -> usize {
+        self.frame_bytes
+    }
+
+    pub fn writer(&self) -> &W {
+        &self.out
+    }
+
+    /// Clears the screen with the next frame, which is then drawn in full.
+    fn start_over(&mut self) {
+        self.shown = CellGrid::new(self.frame.width(), self.frame.height());
+        queue!(
+            self.buffer,
+            SetBackgroundColor(self.background),
+            Clear(ClearType::All)
         )
         .unwrap();
     }
+}
 
-    pub fn clear(&mut self) {
-        queue!(self.stdout, Clear(ClearType::All)).unwrap();
-    }
+/// Switches to `style`, on top of `background` unless it has a background
+/// of its own.
+fn queue_style(buffer: &mut Vec<u8>, style: ContentStyle, background: Color) {
+    queue!(
+        buffer,
+        SetAttribute(Attribute::Reset),
+        SetForegroundColor(style.foreground_color.unwrap_or(Color::Reset)),
+        SetBackgroundColor(style.background_color.unwrap_or(background)),
+        SetAttributes(style.attributes),
+    )
+    .unwrap();
+}
 
-    pub fn restore(&mut self) {
+impl<W: Write + 'static> Renderer for UI<W> {
+    fn prepare(&mut self) {
+        let entered = terminal::enter();
+        self.prepared = true;
         execute!(
-            self.stdout,
-            ResetColor,
+            self.out,
+            SetBackgroundColor(self.background),
             Clear(ClearType::All),
-            MoveTo(0, 0),
-            cursor::Show
+            cursor::Hide,
         )
         .unwrap();
-        disable_raw_mode().unwrap();
+        if entered {
+            // asks the terminal to report key releases, which only some do
+            let _ = execute!(
+                self.out,
+                PushKeyboardEnhancementFlags(KeyboardEnhancementFlags::REPORT_EVENT_TYPES)
+            );
+        }
+        let (columns, rows) =
+            crossterm::terminal::size().unwrap_or((self.frame.width(), self.frame.height()));
+        self.frame = CellGrid::new(columns, rows);
+        self.shown = CellGrid::new(columns, rows);
+    }
+
+    fn clear(&mut self) {
+        self.frame.fill(Cell::default());
+    }
+
+    fn print_at(&mut self, position: Point2d<u16>, content: &StyledContent<String>) {
+        self.frame.print_at(position, content);
+    }
+
+    fn present(&mut self) {
+        // where the cursor is and which style is set, if known
+        let mut cursor = None;
+        let mut style = None;
+        for (position, cell) in self.frame.cells() {
+            if self.shown.cell(position) == Some(cell) {
+                continue;
+            }
+            if cursor != Some(position) {
+                queue!(self.buffer, MoveTo(position.x, position.y)).unwrap();
+            }
+            if style != Some(cell.style) {
+                queue_style(&mut self.buffer, cell.style, self.background);
+                style = Some(cell.style);
+            }
+            queue!(self.buffer, Print(cell.glyph)).unwrap();
+            self.shown.set(position, cell);
+            cursor = Some(Point2d::new(position.x.saturating_add(1), position.y));
+        }
+
+        self.frame_bytes = self.buffer.len();
+        if !self.buffer.is_empty() {
+            self.out
+                .write_all(&self.buffer)
+                .expect("failed to write to stdout");
+            self.out.flush().expect("Failed to flush stdout");
+            self.buffer.clear();
+        }
+    }
+
+    /// Starts over with a blank screen, since the terminal may have moved
+    /// or dropped what it showed.
+    fn resize(&mut self, columns: u16, rows: u16) {
+        self.frame = CellGrid::new(columns, rows);
+        self.start_over();
+    }
+
+    /// Clears the screen in `color`, which is drawn again with the next
+    /// frame.
+    fn set_background(&mut self, color: Color) {
+        self.background = color;
+        self.start_over();
+    }
+
+    fn restore(&mut self) {
+        if std::mem::take(&mut self.prepared) {
+            terminal::leave(&mut self.out);
+        }
+    }
+}
+
+impl<W: Write> Drop for UI<W> {
+    fn drop(&mut self) {
+        if self.prepared {
+            terminal::leave(&mut self.out);
+        }
     }
 }
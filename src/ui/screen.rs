@@ -0,0 +1,127 @@
+use crossterm::style::Stylize;
+
+use crate::{point::Point2d, scores::HighScore};
+
+use super::Renderer;
+
+/// A message that is shown on top of the game, or instead of it.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Screen {
+    Title,
+    HighScores {
+        entries: Vec<HighScore>,
+    },
+    Paused,
+    GameOver {
+        score: u32,
+        /// The name typed so far, while a new high score is entered.
+        name_entry: Option<String>,
+    },
+    /// The terminal is `size` and needs to be at least `min_size`.
+    TooSmall {
+        size: Point2d<u16>,
+        min_size: Point2d<u16>,
+    },
+}
+
+fn strings(lines: &[&str]) -> Vec<String> {
+    lines.iter().map(|line| line.to_string()).collect()
+}
+
+impl Screen {
+    pub fn lines(&self) -> Vec<String> {
+        match self {
+            Screen::Title => strings(&[
+                "HIDE-N-SEEK",
+                "",
+                "Collect the coins and stay away from the enemies",
+                "",
+                "Enter  play",
+                "T      high scores",
+                "P      pause",
+                "Q      quit",
+            ]),
+            Screen::HighScores { entries } => {
+                let mut lines = strings(&["HIGH SCORES", ""]);
+                if entries.is_empty() {
+                    lines.push("No scores yet".to_string());
+                }
+                lines.extend(entries.iter().enumerate().map(|(i, entry)| {
+                    format!(
+                        "{:>2}. {:<12} {:>5}  {:>4}s  {} #{}",
+                        i + 1,
+                        entry.name,
+                        entry.score,
+                        entry.duration.as_secs(),
+                        entry.map,
+                        entry.seed
+                    )
+                }));
+                lines.extend(strings(&["", "Enter  back"]));
+                lines
+            }
+            Screen::Paused => strings(&["PAUSED", "", "P      resume", "Q      give up"]),
+            Screen::GameOver { score, name_entry } => {
+                let mut lines = strings(&["GAME OVER", ""]);
+                lines.push(format!("Score: {}", score));
+                lines.push(String::new());
+                match name_entry {
+                    Some(name) => {
+                        lines.push("New high score! Type your name:".to_string());
+                        lines.push(format!("{}_", name));
+                        lines.extend(strings(&["", "Enter  save", "Esc    skip"]));
+                    }
+                    None => lines.extend(strings(&["Enter  play again", "Q      quit"])),
+                }
+                lines
+            }
+            Screen::TooSmall { size, min_size } => vec![
+                "TERMINAL TOO SMALL".to_string(),
+                String::new(),
+                format!("Needs {}x{}, is {}x{}", min_size.x, min_size.y, size.x, size.y),
+                "Enlarge it to continue".to_string(),
+                String::new(),
+                "Q      quit".to_string(),
+            ],
+        }
+    }
+
+    /// Draws the lines centered in a `width` x `height` area, each on a
+    /// blank background so the game below does not show through.
+    pub fn draw(&self, renderer: &mut dyn Renderer, width: u16, height: u16) {
+        let lines = self.lines();
+        let box_width = lines
+            .iter()
+            .map(|line| line.chars().count())
+            .max()
+            .unwrap_or(0)
+            + 4;
+        let left = (width as usize).saturating_sub(box_width) / 2;
+        let top = (height as usize).saturating_sub(lines.len() + 2) / 2;
+
+        let blank = " ".repeat(box_width);
+        renderer.print_at(
+            Point2d::new(left as u16, top as u16),
+            &blank.clone().stylize(),
+        );
+        for (i, line) in lines.iter().enumerate() {
+            let padding = box_width - line.chars().count();
+            let centered = format!(
+                "{}{}{}",
+                " ".repeat(padding / 2),
+                line,
+                " ".repeat(padding - padding / 2)
+            );
+            let styled = if i == 0 {
+                centered.bold()
+            } else {
+                centered.stylize()
+            };
+            renderer.print_at(Point2d::new(left as u16, (top + 1 + i) as u16), &styled);
+        }
+        renderer.print_at(
+            Point2d::new(left as u16, (top + 1 + lines.len()) as u16),
+            &blank.stylize(),
+        );
+    }
+}
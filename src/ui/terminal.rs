@@ -0,0 +1,90 @@
+use std::{
+    io::{stdout, Write},
+    panic,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Once, OnceLock,
+    },
+};
+
+use crossterm::{
+    cursor::{self, MoveTo},
+    event::PopKeyboardEnhancementFlags,
+    execute,
+    style::ResetColor,
+    terminal::{disable_raw_mode, enable_raw_mode, Clear, ClearType},
+};
+
+/// Whether the terminal is in raw mode and has to be restored.
+static PREPARED: AtomicBool = AtomicBool::new(false);
+
+fn interrupted_flag() -> &'static Arc<AtomicBool> {
+    static INTERRUPTED: OnceLock<Arc<AtomicBool>> = OnceLock::new();
+    INTERRUPTED.get_or_init(Default::default)
+}
+
+/// Switches the terminal to raw mode, unless it already is, and returns
+/// whether it was not. From then on, it is restored when the game panics,
+/// and SIGINT and SIGTERM ask the game to quit.
+pub fn enter() -> bool {
+    if PREPARED.load(Ordering::SeqCst) {
+        return false;
+    }
+    static HOOKS: Once = Once::new();
+    HOOKS.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            // restores first, so the panic message is readable
+            leave(&mut stdout());
+            previous(info);
+        }));
+        watch_signals();
+    });
+    enable_raw_mode().unwrap();
+    PREPARED.store(true, Ordering::SeqCst);
+    true
+}
+
+/// Marks the terminal as prepared without a TTY to switch to raw mode.
+#[cfg(test)]
+pub(super) fn pretend_entered() {
+    PREPARED.store(true, Ordering::SeqCst);
+}
+
+/// Restores the terminal through `out`, unless it already was. Errors are
+/// ignored, since this also runs while panicking.
+pub fn leave(out: &mut impl Write) {
+    if !PREPARED.swap(false, Ordering::SeqCst) {
+        return;
+    }
+    let _ = execute!(out, PopKeyboardEnhancementFlags);
+    let _ = execute!(
+        out,
+        ResetColor,
+        Clear(ClearType::All),
+        MoveTo(0, 0),
+        cursor::Show
+    );
+    let _ = disable_raw_mode();
+}
+
+/// Whether SIGINT or SIGTERM was received. The game quits through its loop
+/// then, so the terminal is restored on the way out.
+pub fn is_interrupted() -> bool {
+    interrupted_flag().load(Ordering::SeqCst)
+}
+
+#[cfg(unix)]
+fn watch_signals() {
+    use signal_hook::{
+        consts::{SIGINT, SIGTERM},
+        flag,
+    };
+
+    for signal in [SIGINT, SIGTERM] {
+        let _ = flag::register(signal, interrupted_flag().clone());
+    }
+}
+
+#[cfg(not(unix))]
+fn watch_signals() {}
@@ -0,0 +1,206 @@
+#![cfg(test)]
+
+use std::{
+    panic,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use crossterm::{
+    event::PopKeyboardEnhancementFlags,
+    style::{ResetColor, Stylize},
+};
+
+use crate::{game::Game, unit::Enemy};
+
+use super::*;
+
+fn ui(columns: u16, rows: u16) -> UI<Vec<u8>> {
+    UI::with_writer(Vec::new(), columns, rows)
+}
+
+#[test]
+fn test_only_changed_cells_are_written() {
+    let mut ui = ui(10, 3);
+    ui.clear();
+    ui.print_at(Point2d::new(2, 1), &"ab".to_string().red());
+    ui.print_at(Point2d::new(5, 1), &"c".to_string().red());
+    ui.present();
+    let written = String::from_utf8_lossy(ui.writer()).to_string();
+    // one move for the neighbouring cells and one style for all of them
+    assert_eq!(written.matches('H').count(), 2);
+    assert_eq!(written.matches("\u{1b}[0m").count(), 1);
+    assert!(written.ends_with('c'));
+
+    ui.clear();
+    ui.print_at(Point2d::new(2, 1), &"ab".to_string().red());
+    ui.print_at(Point2d::new(5, 1), &"c".to_string().red());
+    ui.present();
+    assert_eq!(ui.frame_bytes(), 0);
+
+    // the cell that was left out is blanked
+    ui.clear();
+    ui.print_at(Point2d::new(2, 1), &"ab".to_string().red());
+    ui.present();
+    assert!(ui.frame_bytes() > 0);
+    assert!(String::from_utf8_lossy(ui.writer()).ends_with(' '));
+}
+
+#[test]
+fn test_background_is_cleared_in_the_new_colour() {
+    let mut ui = ui(4, 2);
+    ui.set_background(Color::DarkBlue);
+    ui.clear();
+    ui.present();
+
+    let mut expected = Vec::new();
+    queue!(
+        expected,
+        SetBackgroundColor(Color::DarkBlue),
+        Clear(ClearType::All)
+    )
+    .unwrap();
+    assert_eq!(ui.writer(), &expected);
+}
+
+/// A writer whose bytes can still be read once the renderer writing to it
+/// is gone.
+#[derive(Clone, Default)]
+struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+impl SharedBuffer {
+    fn bytes(&self) -> Vec<u8> {
+        self.0.lock().unwrap().clone()
+    }
+}
+
+impl Write for SharedBuffer {
+    fn write(&mut self, bytes: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().write(bytes)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// A renderer on `out` that acts as if it prepared the terminal.
+fn prepared_ui(out: SharedBuffer) -> UI<SharedBuffer> {
+    terminal::pretend_entered();
+    let mut ui = UI::with_writer(out, 10, 3);
+    ui.prepared = true;
+    ui
+}
+
+// a single test, since whether the terminal is prepared is shared by all of
+// them
+#[test]
+fn test_terminal_is_only_restored_once_prepared() {
+    let mut out = Vec::new();
+    terminal::leave(&mut out);
+    assert!(out.is_empty());
+
+    let mut ui = ui(10, 3);
+    ui.restore();
+    assert!(ui.writer().is_empty());
+
+    let mut restored = Vec::new();
+    queue!(
+        restored,
+        PopKeyboardEnhancementFlags,
+        ResetColor,
+        Clear(ClearType::All),
+        MoveTo(0, 0),
+        cursor::Show
+    )
+    .unwrap();
+
+    // when the renderer is dropped, and only once
+    let out = SharedBuffer::default();
+    let mut ui = prepared_ui(out.clone());
+    ui.restore();
+    drop(ui);
+    assert_eq!(out.bytes(), restored);
+
+    // when the game panics
+    let out = SharedBuffer::default();
+    let result = panic::catch_unwind({
+        let out = out.clone();
+        move || {
+            let _ui = prepared_ui(out);
+            panic!("the game crashed");
+        }
+    });
+    assert!(result.is_err());
+    assert_eq!(out.bytes(), restored);
+}
+
+/// Renders like `UI` did before it kept track of what the terminal shows:
+/// every frame clears the screen and prints every unit with a cursor move
+/// and style of its own.
+#[derive(Default)]
+struct FullRedraw {
+    buffer: Vec<u8>,
+    frame_bytes: usize,
+}
+
+impl Renderer for FullRedraw {
+    fn prepare(&mut self) {}
+
+    fn clear(&mut self) {
+        queue!(self.buffer, Clear(ClearType::All)).unwrap();
+    }
+
+    fn print_at(&mut self, position: Point2d<u16>, content: &StyledContent<String>) {
+        queue!(self.buffer, MoveTo(position.x, position.y), Print(content)).unwrap();
+    }
+
+    fn present(&mut self) {
+        self.frame_bytes = self.buffer.len();
+        self.buffer.clear();
+    }
+
+    fn resize(&mut self, _columns: u16, _rows: u16) {}
+
+    fn set_background(&mut self, _color: Color) {}
+
+    fn restore(&mut self) {}
+}
+
+/// The number of bytes written for each of the first 11 frames of a game
+/// drawn by `renderer`.
+fn frame_sizes<R: Renderer>(renderer: R, frame_bytes: fn(&R) -> usize) -> Vec<usize> {
+    let mut game = Game::builder()
+        .n_random_walls(30)
+        .height(40)
+        .player_starting_speed(0.5)
+        .enemies(
+            (1..10)
+                .map(|i| Enemy::with_speed(i as f64 * 0.05))
+                .collect(),
+        )
+        .update_interval(Duration::from_millis(280))
+        .seed(42)
+        .renderer(Box::new(renderer))
+        .build();
+    game.init();
+    (0..11)
+        .map(|_| {
+            game.update();
+            game.draw();
+            frame_bytes(game.renderer::<R>().unwrap())
+        })
+        .collect()
+}
+
+/// Redrawing all of this game took 7108 bytes every frame. The first frame
+/// now takes 2371 bytes and the ten after it 292 bytes together.
+#[test]
+fn test_frames_after_the_first_are_small() {
+    let redrawn = frame_sizes(FullRedraw::default(), |renderer| renderer.frame_bytes);
+    assert_eq!(redrawn, vec![7108; 11]);
+
+    let diffed = frame_sizes(ui(80, 43), UI::frame_bytes);
+    assert_eq!(diffed[0], 2371);
+    assert_eq!(diffed[1..].iter().sum::<usize>(), 292);
+}
@@ -0,0 +1,292 @@
+mod tests;
+
+use std::{env, error::Error, fs, path::PathBuf};
+
+use crossterm::style::{Color, StyledContent, Stylize};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::unit::CollectibleKind;
+
+/// How one kind of unit is drawn. Without colours, the ones of the terminal
+/// are used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Look {
+    pub glyph: char,
+    #[serde(default)]
+    pub foreground: Option<Color>,
+    #[serde(default)]
+    pub background: Option<Color>,
+}
+
+impl Look {
+    pub fn new(glyph: char, foreground: Color) -> Self {
+        Self {
+            glyph,
+            foreground: Some(foreground),
+            background: None,
+        }
+    }
+
+    pub fn on(mut self, background: Color) -> Self {
+        self.background = Some(background);
+        self
+    }
+
+    pub fn styled(&self) -> StyledContent<String> {
+        let mut styled = self.glyph.to_string().stylize();
+        if let Some(color) = self.foreground {
+            styled = styled.with(color);
+        }
+        if let Some(color) = self.background {
+            styled = styled.on(color);
+        }
+        styled
+    }
+}
+
+/// How the players are drawn.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PlayerLook {
+    /// The glyph of a player standing still, then of one moving up and on
+    /// clockwise in steps of 45°.
+    pub glyphs: [char; 9],
+    /// The colour of each player in turn, which their HUD line is shown in
+    /// as well when there are several players.
+    pub colors: Vec<Color>,
+    #[serde(default)]
+    pub background: Option<Color>,
+}
+
+/// The glyphs and colours the game is drawn with.
+///
+/// Themes are loaded from JSON files that override some of the looks of a
+/// built-in theme, see `parse`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Theme {
+    /// The colour of the screen behind everything.
+    pub background: Color,
+    pub wall: Look,
+    pub enemy: Look,
+    pub player: PlayerLook,
+    pub coin: Look,
+    pub heart: Look,
+    pub speed_boost: Look,
+    pub invisibility: Look,
+    pub freeze: Look,
+    pub shield: Look,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::unicode()
+    }
+}
+
+impl Theme {
+    /// The names of the built-in themes.
+    pub const BUILT_IN: [&'static str; 3] = ["unicode", "ascii", "high-contrast"];
+
+    pub fn unicode() -> Self {
+        Self {
+            background: Color::Black,
+            wall: Look::new('▓', Color::Magenta),
+            enemy: Look::new('⁂', Color::DarkGreen),
+            player: PlayerLook {
+                glyphs: ['•', '↑', '↗', '→', '↘', '↓', '↙', '←', '↖'],
+                colors: vec![
+                    Color::DarkBlue,
+                    Color::DarkYellow,
+                    Color::DarkCyan,
+                    Color::DarkRed,
+                ],
+                background: None,
+            },
+            coin: Look::new('●', Color::Yellow),
+            heart: Look::new('❤', Color::Red),
+            speed_boost: Look::new('»', Color::Cyan),
+            invisibility: Look::new('◌', Color::Grey),
+            freeze: Look::new('❄', Color::Blue),
+            shield: Look::new('◈', Color::White),
+        }
+    }
+
+    /// The unicode theme with plain ASCII glyphs, for terminals without
+    /// UTF-8.
+    pub fn ascii() -> Self {
+        Self {
+            wall: Look::new('#', Color::Magenta),
+            enemy: Look::new('X', Color::DarkGreen),
+            player: PlayerLook {
+                glyphs: ['@', '^', '/', '>', '\\', 'v', '/', '<', '\\'],
+                ..Self::unicode().player
+            },
+            coin: Look::new('$', Color::Yellow),
+            heart: Look::new('+', Color::Red),
+            speed_boost: Look::new('>', Color::Cyan),
+            invisibility: Look::new('?', Color::Grey),
+            freeze: Look::new('*', Color::Blue),
+            shield: Look::new('O', Color::White),
+            ..Self::unicode()
+        }
+    }
+
+    /// Bright colours that stay apart with colour blindness, from the
+    /// Okabe-Ito palette.
+    pub fn high_contrast() -> Self {
+        let rgb = |r, g, b| Color::Rgb { r, g, b };
+        let orange = rgb(230, 159, 0);
+        let sky_blue = rgb(86, 180, 233);
+        let green = rgb(0, 158, 115);
+        let yellow = rgb(240, 228, 66);
+        let blue = rgb(0, 114, 178);
+        let purple = rgb(204, 121, 167);
+
+        Self {
+            background: Color::Black,
+            wall: Look::new('█', Color::Grey),
+            enemy: Look::new('⁂', Color::Black).on(orange),
+            player: PlayerLook {
+                colors: vec![Color::White, sky_blue, green, purple],
+                ..Self::unicode().player
+            },
+            coin: Look::new('●', yellow),
+            heart: Look::new('♥', purple),
+            speed_boost: Look::new('»', sky_blue),
+            invisibility: Look::new('◌', Color::White),
+            freeze: Look::new('❄', blue),
+            shield: Look::new('◈', green),
+        }
+    }
+
+    pub fn built_in(name: &str) -> Option<Self> {
+        match name {
+            "unicode" => Some(Self::unicode()),
+            "ascii" => Some(Self::ascii()),
+            "high-contrast" => Some(Self::high_contrast()),
+            _ => None,
+        }
+    }
+
+    /// The unicode theme, or the ASCII one if the locale says the terminal
+    /// does not use UTF-8.
+    pub fn for_locale() -> Self {
+        let locale = ["LC_ALL", "LC_CTYPE", "LANG"]
+            .iter()
+            .filter_map(|name| env::var(name).ok())
+            .find(|value| !value.is_empty());
+        match locale {
+            Some(locale) if !locale.to_lowercase().replace('-', "").contains("utf8") => {
+                Self::ascii()
+            }
+            _ => Self::unicode(),
+        }
+    }
+
+    /// Where the theme is loaded from unless another one is given, in the
+    /// config directory of the user.
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("hide-n-seek").join("theme.json"))
+    }
+
+    /// Parses a theme from a JSON object with the looks that differ from
+    /// the built-in theme `base`, unicode if it is left out, e.g.
+    /// `{"base": "ascii", "wall": {"glyph": "%"}, "background": "dark_blue"}`.
+    /// The fields of a look that are left out keep their value too.
+    pub fn parse(text: &str) -> Result<Self, Box<dyn Error>> {
+        let mut config: Value = serde_json::from_str(text)?;
+        let base = match config
+            .as_object_mut()
+            .and_then(|config| config.remove("base"))
+        {
+            Some(Value::String(name)) => Self::built_in(&name).ok_or_else(|| {
+                format!(
+                    "`{}` is not a built-in theme, which are {}",
+                    name,
+                    Self::BUILT_IN.join(", ")
+                )
+            })?,
+            Some(_) => return Err("the base theme must be a name".into()),
+            None => Self::unicode(),
+        };
+        let mut theme = serde_json::to_value(base)?;
+        merge(&mut theme, config);
+        Ok(serde_json::from_value(theme)?)
+    }
+
+    pub fn load(path: &str) -> Result<Self, Box<dyn Error>> {
+        Self::parse(&fs::read_to_string(path)?)
+    }
+
+    /// The built-in theme called `name`, or else the theme in the file at
+    /// `name`.
+    pub fn find(name: &str) -> Result<Self, Box<dyn Error>> {
+        match Self::built_in(name) {
+            Some(theme) => Ok(theme),
+            None => Self::load(name),
+        }
+    }
+
+    /// Loads the theme from the default path, or picks one for the locale
+    /// if there is no file there.
+    pub fn load_default() -> Result<Self, Box<dyn Error>> {
+        match Self::default_path().filter(|path| path.exists()) {
+            Some(path) => Self::load(&path.to_string_lossy()),
+            None => Ok(Self::for_locale()),
+        }
+    }
+
+    pub fn collectible(&self, kind: CollectibleKind) -> &Look {
+        match kind {
+            CollectibleKind::Coin => &self.coin,
+            CollectibleKind::Heart => &self.heart,
+            CollectibleKind::SpeedBoost => &self.speed_boost,
+            CollectibleKind::Invisibility => &self.invisibility,
+            CollectibleKind::Freeze => &self.freeze,
+            CollectibleKind::Shield => &self.shield,
+        }
+    }
+
+    /// The colour of player number `number`.
+    pub fn player_color(&self, number: usize) -> Color {
+        match self.player.colors.len() {
+            0 => Color::Reset,
+            n => self.player.colors[number % n],
+        }
+    }
+
+    /// The look of player number `number` facing `facing`, as in
+    /// `Player::facing`.
+    pub fn player(&self, number: usize, facing: Option<usize>) -> Look {
+        let glyph = match facing {
+            Some(facing) => self.player.glyphs[1 + facing % 8],
+            None => self.player.glyphs[0],
+        };
+        Look {
+            glyph,
+            foreground: Some(self.player_color(number)),
+            background: self.player.background,
+        }
+    }
+}
+
+/// Overrides the fields of `base` with the ones in `config`, going into the
+/// objects both of them have.
+fn merge(base: &mut Value, config: Value) {
+    match (base, config) {
+        (Value::Object(base), Value::Object(config)) => {
+            for (key, value) in config {
+                match base.get_mut(&key) {
+                    Some(field) => merge(field, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, config) => *base = config,
+    }
+}
@@ -0,0 +1,69 @@
+#![cfg(test)]
+
+use super::*;
+
+#[test]
+fn test_built_in_themes() {
+    for name in Theme::BUILT_IN {
+        let theme = Theme::built_in(name).unwrap();
+        // every theme can be written to a file and read back
+        let text = serde_json::to_string(&theme).unwrap();
+        assert_eq!(Theme::parse(&text).unwrap(), theme);
+    }
+    assert_eq!(Theme::built_in("sepia"), None);
+
+    let ascii = Theme::ascii();
+    let glyphs = [
+        ascii.wall.glyph,
+        ascii.enemy.glyph,
+        ascii.coin.glyph,
+        ascii.heart.glyph,
+        ascii.speed_boost.glyph,
+        ascii.invisibility.glyph,
+        ascii.freeze.glyph,
+        ascii.shield.glyph,
+    ];
+    assert!(glyphs
+        .iter()
+        .chain(&ascii.player.glyphs)
+        .all(char::is_ascii));
+    assert_eq!(ascii.player(1, Some(2)), Look::new('>', Color::DarkYellow));
+    assert_eq!(ascii.player(0, None).glyph, '@');
+}
+
+#[test]
+fn test_theme_file_overrides_its_base() {
+    let theme = Theme::parse(
+        r#"{
+            "base": "ascii",
+            "background": "dark_blue",
+            "wall": {"glyph": "%"},
+            "enemy": {"foreground": "rgb_(255,0,0)", "background": "white"}
+        }"#,
+    )
+    .unwrap();
+
+    assert_eq!(theme.background, Color::DarkBlue);
+    assert_eq!(theme.wall, Look::new('%', Color::Magenta));
+    assert_eq!(
+        theme.enemy,
+        Look::new('X', Color::Rgb { r: 255, g: 0, b: 0 }).on(Color::White)
+    );
+    assert_eq!(theme.coin, Theme::ascii().coin);
+
+    let theme = Theme::parse(r#"{"coin": {"glyph": "c"}}"#).unwrap();
+    assert_eq!(theme.coin, Look::new('c', Color::Yellow));
+    assert_eq!(theme.wall, Theme::unicode().wall);
+}
+
+#[test]
+fn test_invalid_themes_are_rejected() {
+    let error = Theme::parse(r#"{"base": "sepia"}"#).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "`sepia` is not a built-in theme, which are unicode, ascii, high-contrast"
+    );
+    assert!(Theme::parse(r#"{"tree": {"glyph": "T"}}"#).is_err());
+    assert!(Theme::parse(r#"{"wall": {"glyph": "%%"}}"#).is_err());
+    assert!(Theme::parse(r#"{"wall": {"foreground": "mauve"}}"#).is_err());
+}
@@ -2,14 +2,82 @@ use serde::{Deserialize, Serialize};
 
 use crate::{game::Game, point::Point2d, traits::{Position, UpdatableByTimeFrame}};
 
-#[derive(Debug, Default, Serialize, Deserialize, PartialEq)]
+use super::EffectKind;
+
+/// What happens when the player picks a collectible up.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum CollectibleKind {
+    /// Increases the score.
+    #[default]
+    Coin,
+    /// Restores one health.
+    Heart,
+    /// Doubles the speed of the player for a while.
+    SpeedBoost,
+    /// Hides the player from the enemies for a while, so they lose track.
+    Invisibility,
+    /// Stops the enemies, which do no harm then, for a while.
+    Freeze,
+    /// Protects the player from the enemies for a while.
+    Shield,
+}
+
+impl CollectibleKind {
+    pub const ALL: [CollectibleKind; 6] = [
+        CollectibleKind::Coin,
+        CollectibleKind::Heart,
+        CollectibleKind::SpeedBoost,
+        CollectibleKind::Invisibility,
+        CollectibleKind::Freeze,
+        CollectibleKind::Shield,
+    ];
+
+    /// The status effect picking it up applies, if any.
+    pub fn effect(self) -> Option<EffectKind> {
+        match self {
+            CollectibleKind::Coin | CollectibleKind::Heart => None,
+            CollectibleKind::SpeedBoost => Some(EffectKind::SpeedBoost),
+            CollectibleKind::Invisibility => Some(EffectKind::Invisibility),
+            CollectibleKind::Freeze => Some(EffectKind::Freeze),
+            CollectibleKind::Shield => Some(EffectKind::Shield),
+        }
+    }
+
+    /// How many updates the effect lasts, zero for the ones that happen at
+    /// once.
+    pub fn duration(self) -> u32 {
+        match self {
+            CollectibleKind::Coin | CollectibleKind::Heart => 0,
+            CollectibleKind::SpeedBoost => 40,
+            CollectibleKind::Invisibility => 30,
+            CollectibleKind::Freeze => 20,
+            CollectibleKind::Shield => 40,
+        }
+    }
+
+    /// How often each kind spawns relative to the others, unless the game
+    /// is built with other weights.
+    pub fn default_weights() -> Vec<(CollectibleKind, u32)> {
+        vec![
+            (CollectibleKind::Coin, 10),
+            (CollectibleKind::Heart, 3),
+            (CollectibleKind::SpeedBoost, 2),
+            (CollectibleKind::Invisibility, 2),
+            (CollectibleKind::Freeze, 2),
+            (CollectibleKind::Shield, 2),
+        ]
+    }
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Collectible {
     position: Point2d<u16>,
+    kind: CollectibleKind,
 }
 
 impl Position<u16> for Collectible {
     fn position(&self) -> Point2d<u16> {
-        self.position.clone()
+        self.position
     }
 
     fn set_position(&mut self, position: Point2d<u16>) {
@@ -18,6 +86,14 @@ impl Position<u16> for Collectible {
 }
 
 impl Collectible {
+    pub fn new(position: Point2d<u16>, kind: CollectibleKind) -> Self {
+        Self { position, kind }
+    }
+
+    pub fn kind(&self) -> CollectibleKind {
+        self.kind
+    }
+
     pub fn randomize_position(&mut self, game: &Game) {
         game.randomize_position_u16(self);
 
@@ -25,16 +101,25 @@ impl Collectible {
             game.randomize_position_u16(self);
         }
     }
+
+    /// Turns into a random kind of collectible at a random position.
+    pub fn respawn(&mut self, game: &Game) {
+        self.kind = game.random_collectible_kind();
+        self.randomize_position(game);
+    }
 }
 
 impl UpdatableByTimeFrame for Collectible {
     fn update(&mut self, game: &Game) {
-        // increase score if player collides with collectible
-        if game.player_position().round().to_u16() == self.position() {
-            game.player_state().borrow_mut().increase_score();
-            
-            // move collectible to a new random position
-            self.randomize_position(game);
+        // apply the effect to the first player that collides with the collectible
+        let collector = game
+            .living_players()
+            .find(|number| game.player_position(*number).round().to_u16() == self.position());
+        if let Some(number) = collector {
+            game.player_state(number).borrow_mut().collect(self.kind);
+
+            // replace the collectible with a new one
+            self.respawn(game);
         }
     }
-}
\ No newline at end of file
+}
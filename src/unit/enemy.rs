@@ -1,13 +1,35 @@
-use std::time::Duration;
+use std::f64::consts::PI;
 
 use crate::game::Game;
 use crate::traits::UpdatableByTimeFrame;
+use crate::visibility::in_view_cone;
 use crate::{point::Point2d, traits::Position};
 
-#[derive(Debug, Default, serde::Serialize, serde::Deserialize, PartialEq)]
+pub mod awareness;
+pub mod behavior;
+mod tests;
+pub use awareness::Awareness;
+pub use behavior::{Ambush, Behavior, Chase, EnemyBehavior, Flee, Patrol, Wander};
+
+#[derive(Debug, Default, Clone, serde::Serialize, serde::Deserialize, PartialEq)]
 pub struct Enemy {
     position: Point2d<f64>,
     speed: f64,
+    behavior: Behavior,
+    /// Where the enemy roams to when its behaviour has nothing to do.
+    roam: Wander,
+    /// How many tiles far the enemy can see.
+    view_distance: f64,
+    /// How wide the enemy can see around `facing`, in degrees.
+    field_of_view: f64,
+    facing: Point2d<f64>,
+    awareness: Awareness,
+    /// The player the enemy saw last and goes after, counting from 0.
+    #[serde(default)]
+    chased_player: usize,
+    /// The tiles left to walk to reach `path_goal`, next one first.
+    path: Vec<Point2d<u16>>,
+    path_goal: Option<Point2d<u16>>,
 }
 
 impl Enemy {
@@ -15,17 +37,132 @@ impl Enemy {
         Self {
             position: Point2d::new(0.0, 0.0),
             speed,
+            behavior: Behavior::default(),
+            roam: Wander::default(),
+            view_distance: 12.0,
+            field_of_view: 120.0,
+            facing: Point2d::new(1.0, 0.0),
+            awareness: Awareness::default(),
+            chased_player: 0,
+            path: vec![],
+            path_goal: None,
         }
     }
 
-    pub fn move_towards_player(
-        &mut self,
-        player_position: Point2d<f64>,
-        since_last_time: &Duration,
-    ) {
-        let direction = player_position.round() - self.position().round();
+    pub fn speed(&self) -> f64 {
+        self.speed
+    }
 
-        self.position += direction.normalize() * (self.speed * since_last_time.as_secs_f64());
+    pub fn with_behavior(mut self, behavior: impl Into<Behavior>) -> Self {
+        self.behavior = behavior.into();
+        self
+    }
+
+    pub fn behavior(&self) -> &Behavior {
+        &self.behavior
+    }
+
+    /// Sets how many tiles far and how many degrees wide the enemy can see.
+    pub fn with_vision(mut self, view_distance: f64, field_of_view: f64) -> Self {
+        self.view_distance = view_distance;
+        self.field_of_view = field_of_view;
+        self
+    }
+
+    pub fn awareness(&self) -> &Awareness {
+        &self.awareness
+    }
+
+    /// Whether player number `number` is alive, in the enemy's view cone
+    /// and not hidden behind a wall or invisible.
+    pub fn sees_player(&self, number: usize, game: &Game) -> bool {
+        let player = game.player_position(number);
+        let state = game.player_state(number).borrow();
+        state.is_alive()
+            && !state.is_invisible()
+            && in_view_cone(
+                self.position,
+                self.facing,
+                player,
+                self.view_distance,
+                self.field_of_view,
+            )
+            && game.line_of_sight(self.position.round().to_u16(), player.round().to_u16())
+    }
+
+    /// The closest of the players the enemy sees, if any.
+    pub fn nearest_visible_player(&self, game: &Game) -> Option<usize> {
+        (0..game.n_players())
+            .filter(|number| self.sees_player(*number, game))
+            .min_by(|a, b| {
+                let a = self.position.distance(&game.player_position(*a));
+                let b = self.position.distance(&game.player_position(*b));
+                a.total_cmp(&b)
+            })
+    }
+
+    fn target(&mut self, game: &Game) -> Option<Point2d<u16>> {
+        match self.awareness {
+            Awareness::Tracking { .. } => self.behavior.pursue(self.position, self.chased_player, game),
+            Awareness::Investigating { last_known } => Some(last_known),
+            Awareness::Searching { spot, .. } => Some(spot),
+            Awareness::Unaware => self
+                .behavior
+                .idle(self.position, game)
+                .or_else(|| self.roam.idle(self.position, game)),
+        }
+    }
+
+    /// Follows the shortest path around the walls to `target`. The path is
+    /// only searched again once the target moves to another tile, e.g. when
+    /// a chased player does. Returns false if the path was searched and
+    /// there is none.
+    pub fn move_towards(&mut self, target: Option<Point2d<u16>>, game: &Game) -> bool {
+        if self.path_goal != target {
+            let tile = self.position.round().to_u16();
+            // walk back to the center of the current tile first, so the
+            // enemy does not cut the corner of a wall next to it
+            self.path = vec![tile];
+            self.path_goal = target;
+            if let Some(target) = target {
+                match game.find_path(tile, target) {
+                    Some(path) => self.path.extend(path),
+                    None => return false,
+                }
+            }
+        }
+
+        let mut distance = self.speed * game.update_interval_millis.as_secs_f64();
+        while let Some(tile) = self.path.first().copied() {
+            let waypoint = tile.to_f64();
+            let remaining = self.position.distance(&waypoint);
+            if remaining > distance {
+                let movement = (waypoint - self.position).normalize() * distance;
+                self.position = game.slide(self.position, movement);
+                return true;
+            }
+            self.position = game.slide(self.position, waypoint - self.position);
+            if self.position.round().to_u16() != tile {
+                // a wall is in the way, search the path again next time
+                self.path_goal = None;
+                return true;
+            }
+            self.position = waypoint;
+            distance -= remaining;
+            self.path.remove(0);
+        }
+        true
+    }
+
+    /// Heads somewhere else than `tile`, the target of this update, as it
+    /// can not be reached.
+    fn give_up_on(&mut self, tile: Point2d<u16>, game: &Game) {
+        if self.awareness == Awareness::Unaware {
+            self.behavior.unreachable(tile);
+            self.roam.unreachable(tile);
+        } else {
+            self.awareness.unreachable(tile, game);
+        }
     }
 }
 
@@ -41,12 +178,53 @@ impl Position<f64> for Enemy {
 
 impl UpdatableByTimeFrame for Enemy {
     fn update(&mut self, game: &Game) {
+        let frozen = game
+            .living_players()
+            .any(|number| game.player_state(number).borrow().are_enemies_frozen());
+        // frozen enemies neither move nor hurt the players
+        if frozen {
+            return;
+        }
+        self.look_and_move(game);
+
+        // hurt the players when colliding with them and knock them back
+        let touched: Vec<usize> = game
+            .living_players()
+            .filter(|number| self.position().round() == game.player_position(*number).round())
+            .collect();
+        for number in touched {
+            let hurt = game.player_state(number).borrow_mut().hit();
+            if hurt {
+                game.knock_back_player(number, self.position);
+            }
+        }
+    }
+}
+
+impl Enemy {
+    fn look_and_move(&mut self, game: &Game) {
+        // look for the players and go after the nearest one
+        let seen = self.nearest_visible_player(game);
+        if let Some(number) = seen {
+            self.chased_player = number;
+        }
+        let sighting = seen.map(|number| game.player_position(number).round().to_u16());
+        self.awareness
+            .update(sighting, self.position.round().to_u16(), game);
+
         // move enemies
-        self.move_towards_player(game.player_position(), &game.update_interval_millis);
+        let previous_position = self.position;
+        let target = self.target(game);
+        if let (false, Some(tile)) = (self.move_towards(target, game), target) {
+            self.give_up_on(tile, game);
+        }
 
-        // reduce player health for each enemy collision
-        if self.position().round() == game.player_position().round() {
-            game.player_state().borrow_mut().decrease_health();
+        // face where the enemy is going, or look around while standing
+        let moved = self.position - previous_position;
+        if moved.x != 0.0 || moved.y != 0.0 {
+            self.facing = moved.normalize();
+        } else {
+            self.facing = self.facing.rotate(PI / 4.0);
         }
     }
 }
@@ -0,0 +1,88 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{game::Game, point::Point2d};
+
+/// How many updates an enemy searches around the last known position of
+/// the player before giving up.
+const SEARCH_TICKS: u32 = 40;
+/// How far from the last known position an enemy searches.
+const SEARCH_RADIUS: u16 = 4;
+
+/// What an enemy knows about the whereabouts of the player.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "state")]
+pub enum Awareness {
+    /// The player has not been seen, or the enemy gave up searching.
+    #[default]
+    Unaware,
+    /// The player is in sight.
+    Tracking { last_known: Point2d<u16> },
+    /// The player went out of sight, so the enemy heads to where they were
+    /// last seen.
+    Investigating { last_known: Point2d<u16> },
+    /// The trail went cold, the enemy looks around for a while.
+    Searching {
+        around: Point2d<u16>,
+        spot: Point2d<u16>,
+        ticks_left: u32,
+    },
+}
+
+impl Awareness {
+    /// Moves on to the next state, given where the player was seen this
+    /// update, if anywhere, and the tile the enemy stands on.
+    pub fn update(&mut self, sighting: Option<Point2d<u16>>, tile: Point2d<u16>, game: &Game) {
+        if let Some(player) = sighting {
+            *self = Awareness::Tracking { last_known: player };
+            return;
+        }
+
+        *self = match *self {
+            Awareness::Unaware => Awareness::Unaware,
+            Awareness::Tracking { last_known } => Awareness::Investigating { last_known },
+            Awareness::Investigating { last_known } if tile == last_known => {
+                Self::search_around(last_known, game)
+            }
+            Awareness::Investigating { last_known } => Awareness::Investigating { last_known },
+            Awareness::Searching { ticks_left: 0, .. } => Awareness::Unaware,
+            Awareness::Searching {
+                around,
+                spot,
+                ticks_left,
+            } => Awareness::Searching {
+                around,
+                spot: if tile == spot {
+                    game.random_open_tile_near(around, SEARCH_RADIUS)
+                } else {
+                    spot
+                },
+                ticks_left: ticks_left - 1,
+            },
+        };
+    }
+
+    /// Moves on when `tile`, where the enemy is heading, can not be
+    /// reached: it searches around the last known position of the player
+    /// instead of going there, or picks another spot to search.
+    pub fn unreachable(&mut self, tile: Point2d<u16>, game: &Game) {
+        match *self {
+            Awareness::Investigating { last_known } if tile == last_known => {
+                *self = Self::search_around(last_known, game);
+            }
+            Awareness::Searching {
+                around,
+                ref mut spot,
+                ..
+            } if tile == *spot => *spot = game.random_open_tile_near(around, SEARCH_RADIUS),
+            _ => {}
+        }
+    }
+
+    fn search_around(last_known: Point2d<u16>, game: &Game) -> Self {
+        Awareness::Searching {
+            around: last_known,
+            spot: game.random_open_tile_near(last_known, SEARCH_RADIUS),
+            ticks_left: SEARCH_TICKS,
+        }
+    }
+}
@@ -0,0 +1,265 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{game::Game, point::Point2d};
+
+/// Decides where an enemy is heading to. The enemy walks to the returned
+/// tile along the shortest path around the walls.
+pub trait EnemyBehavior {
+    /// The tile to walk to from `position` while player number `player` is
+    /// in sight, or `None` to stand still. Runs straight at the player by
+    /// default.
+    fn pursue(
+        &mut self,
+        _position: Point2d<f64>,
+        player: usize,
+        game: &Game,
+    ) -> Option<Point2d<u16>> {
+        Some(game.player_position(player).round().to_u16())
+    }
+
+    /// The tile to walk to while the enemy does not know where the player
+    /// is. `None` lets the enemy roam around.
+    fn idle(&mut self, _position: Point2d<f64>, _game: &Game) -> Option<Point2d<u16>> {
+        None
+    }
+
+    /// Tells the behaviour that `tile`, which `idle` returned, can not be
+    /// reached, so it can head somewhere else instead.
+    fn unreachable(&mut self, _tile: Point2d<u16>) {}
+}
+
+/// Runs straight at the player.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Chase;
+
+impl EnemyBehavior for Chase {}
+
+/// Walks along the waypoints in turn, starting over after the last one,
+/// until the player is spotted. The waypoints that can not be reached are
+/// skipped, and without any left the enemy roams around.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Patrol {
+    waypoints: Vec<Point2d<u16>>,
+    next: usize,
+    /// The indices of the waypoints that can not be reached.
+    #[serde(default)]
+    unreachable: Vec<usize>,
+}
+
+impl Patrol {
+    pub fn new(waypoints: Vec<Point2d<u16>>) -> Self {
+        Self {
+            waypoints,
+            next: 0,
+            unreachable: vec![],
+        }
+    }
+
+    fn is_reachable(&self, index: usize) -> bool {
+        !self.unreachable.contains(&index)
+    }
+}
+
+impl EnemyBehavior for Patrol {
+    fn idle(&mut self, position: Point2d<f64>, _game: &Game) -> Option<Point2d<u16>> {
+        if !(0..self.waypoints.len()).any(|index| self.is_reachable(index)) {
+            return None;
+        }
+        let tile = position.round().to_u16();
+        if tile == self.waypoints[self.next] || !self.is_reachable(self.next) {
+            self.next = (1..=self.waypoints.len())
+                .map(|step| (self.next + step) % self.waypoints.len())
+                .find(|index| self.is_reachable(*index))?;
+        }
+        Some(self.waypoints[self.next])
+    }
+
+    fn unreachable(&mut self, tile: Point2d<u16>) {
+        if self.waypoints.get(self.next) == Some(&tile) && self.is_reachable(self.next) {
+            self.unreachable.push(self.next);
+        }
+    }
+}
+
+/// Heads to where the player will be if they keep going in their current
+/// direction, to cut them off.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Ambush {
+    /// How many tiles ahead of the player to aim for.
+    pub lookahead: f64,
+}
+
+impl Default for Ambush {
+    fn default() -> Self {
+        Self { lookahead: 4.0 }
+    }
+}
+
+impl EnemyBehavior for Ambush {
+    fn pursue(
+        &mut self,
+        _position: Point2d<f64>,
+        player: usize,
+        game: &Game,
+    ) -> Option<Point2d<u16>> {
+        let direction = game.player_direction(player);
+        let player = game.player_position(player);
+        let ahead = player + direction.normalize() * self.lookahead;
+        let ahead = Point2d::new(
+            ahead.x.clamp(0.0, (game.width() - 1).into()),
+            ahead.y.clamp(0.0, (game.height() - 1).into()),
+        )
+        .round()
+        .to_u16();
+
+        // the predicted tile may be inside a wall
+        if game.do_walls_collide(ahead) {
+            Some(player.round().to_u16())
+        } else {
+            Some(ahead)
+        }
+    }
+}
+
+/// Strolls to random tiles, picking a new one once it arrives, until the
+/// player is spotted.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Wander {
+    destination: Option<Point2d<u16>>,
+}
+
+impl EnemyBehavior for Wander {
+    fn idle(&mut self, position: Point2d<f64>, game: &Game) -> Option<Point2d<u16>> {
+        let tile = position.round().to_u16();
+        match self.destination {
+            Some(destination) if destination != tile => {}
+            _ => self.destination = Some(game.random_open_tile()),
+        }
+        self.destination
+    }
+
+    fn unreachable(&mut self, tile: Point2d<u16>) {
+        if self.destination == Some(tile) {
+            self.destination = None;
+        }
+    }
+}
+
+/// Chases the player, but runs away from them while they are shielded.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Flee;
+
+impl EnemyBehavior for Flee {
+    fn pursue(
+        &mut self,
+        position: Point2d<f64>,
+        player: usize,
+        game: &Game,
+    ) -> Option<Point2d<u16>> {
+        if !game.player_state(player).borrow().is_shielded() {
+            return Chase.pursue(position, player, game);
+        }
+
+        // step to the free neighbouring tile that is furthest from the player
+        let player = game.player_position(player);
+        let tile = position.round().to_u16();
+        (-1..=1)
+            .flat_map(|dy| (-1..=1).map(move |dx| (dx, dy)))
+            .filter_map(|(dx, dy)| {
+                let x = tile.x.checked_add_signed(dx)?;
+                let y = tile.y.checked_add_signed(dy)?;
+                Some(Point2d::new(x, y))
+            })
+            .filter(|neighbour| !game.do_walls_collide(*neighbour))
+            .max_by(|a, b| {
+                let a = a.to_f64().distance(&player);
+                let b = b.to_f64().distance(&player);
+                a.total_cmp(&b)
+            })
+    }
+}
+
+/// One of the behaviours above. Enemies keep their behaviour in this enum,
+/// so it is saved together with the game.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind")]
+pub enum Behavior {
+    Chase(Chase),
+    Patrol(Patrol),
+    Ambush(Ambush),
+    Wander(Wander),
+    Flee(Flee),
+}
+
+impl Default for Behavior {
+    fn default() -> Self {
+        Behavior::Chase(Chase)
+    }
+}
+
+impl EnemyBehavior for Behavior {
+    fn pursue(
+        &mut self,
+        position: Point2d<f64>,
+        player: usize,
+        game: &Game,
+    ) -> Option<Point2d<u16>> {
+        match self {
+            Behavior::Chase(behavior) => behavior.pursue(position, player, game),
+            Behavior::Patrol(behavior) => behavior.pursue(position, player, game),
+            Behavior::Ambush(behavior) => behavior.pursue(position, player, game),
+            Behavior::Wander(behavior) => behavior.pursue(position, player, game),
+            Behavior::Flee(behavior) => behavior.pursue(position, player, game),
+        }
+    }
+
+    fn idle(&mut self, position: Point2d<f64>, game: &Game) -> Option<Point2d<u16>> {
+        match self {
+            Behavior::Chase(behavior) => behavior.idle(position, game),
+            Behavior::Patrol(behavior) => behavior.idle(position, game),
+            Behavior::Ambush(behavior) => behavior.idle(position, game),
+            Behavior::Wander(behavior) => behavior.idle(position, game),
+            Behavior::Flee(behavior) => behavior.idle(position, game),
+        }
+    }
+
+    fn unreachable(&mut self, tile: Point2d<u16>) {
+        match self {
+            Behavior::Chase(behavior) => behavior.unreachable(tile),
+            Behavior::Patrol(behavior) => behavior.unreachable(tile),
+            Behavior::Ambush(behavior) => behavior.unreachable(tile),
+            Behavior::Wander(behavior) => behavior.unreachable(tile),
+            Behavior::Flee(behavior) => behavior.unreachable(tile),
+        }
+    }
+}
+
+impl From<Chase> for Behavior {
+    fn from(behavior: Chase) -> Self {
+        Behavior::Chase(behavior)
+    }
+}
+
+impl From<Patrol> for Behavior {
+    fn from(behavior: Patrol) -> Self {
+        Behavior::Patrol(behavior)
+    }
+}
+
+impl From<Ambush> for Behavior {
+    fn from(behavior: Ambush) -> Self {
+        Behavior::Ambush(behavior)
+    }
+}
+
+impl From<Wander> for Behavior {
+    fn from(behavior: Wander) -> Self {
+        Behavior::Wander(behavior)
+    }
+}
+
+impl From<Flee> for Behavior {
+    fn from(behavior: Flee) -> Self {
+        Behavior::Flee(behavior)
+    }
+}
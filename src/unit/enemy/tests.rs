@@ -0,0 +1,198 @@
+#![cfg(test)]
+
+use crate::{game::Game, point::Point2d, traits::Position, ui::GridRenderer, unit::{CollectibleKind, Wall}};
+
+use super::*;
+
+fn game() -> Game {
+    game_with_walls(vec![])
+}
+
+fn game_with_walls(walls: Vec<Wall>) -> Game {
+    let mut game = Game::builder()
+        .width(10)
+        .height(10)
+        .enemies(vec![])
+        .walls(walls)
+        .player_starting_position(2.0, 2.0)
+        .seed(1)
+        .renderer(Box::new(GridRenderer::new(10, 13)))
+        .build();
+    game.init();
+    game
+}
+
+#[test]
+fn test_behaviors_serialize_with_the_enemy() {
+    let enemies = vec![
+        Enemy::with_speed(0.1),
+        Enemy::with_speed(0.2).with_behavior(Patrol::new(vec![Point2d::new(3, 4)])),
+        Enemy::with_speed(0.3).with_behavior(Ambush { lookahead: 2.0 }),
+        Enemy::with_speed(0.4).with_behavior(Wander::default()),
+        Enemy::with_speed(0.5).with_behavior(Flee),
+    ];
+    let json = serde_json::to_string(&enemies).unwrap();
+    assert_eq!(serde_json::from_str::<Vec<Enemy>>(&json).unwrap(), enemies);
+}
+
+#[test]
+fn test_patrol_moves_on_to_the_next_waypoint() {
+    let game = game();
+    let mut patrol = Patrol::new(vec![Point2d::new(3, 3), Point2d::new(6, 3)]);
+
+    assert_eq!(patrol.idle(Point2d::new(5.0, 5.0), &game), Some(Point2d::new(3, 3)));
+    assert_eq!(patrol.idle(Point2d::new(3.2, 2.9), &game), Some(Point2d::new(6, 3)));
+    assert_eq!(patrol.idle(Point2d::new(6.0, 3.0), &game), Some(Point2d::new(3, 3)));
+}
+
+/// A game with the tile (7, 7) walled off.
+fn game_with_walled_off_tile() -> Game {
+    let walls = (6..=8)
+        .flat_map(|y| (6..=8).map(move |x| (x, y)))
+        .filter(|&(x, y)| (x, y) != (7, 7))
+        .map(|(x, y)| Wall::new(x, y))
+        .collect();
+    game_with_walls(walls)
+}
+
+#[test]
+fn test_unreachable_destinations_are_skipped() {
+    let game = game_with_walled_off_tile();
+    let walled_off = Point2d::new(7, 7);
+    let position = Point2d::new(5.0, 5.0);
+
+    let mut patrol = Patrol::new(vec![walled_off, Point2d::new(3, 3)]);
+    assert_eq!(patrol.idle(position, &game), Some(walled_off));
+    patrol.unreachable(walled_off);
+    assert_eq!(patrol.idle(position, &game), Some(Point2d::new(3, 3)));
+    assert_eq!(patrol.idle(Point2d::new(3.0, 3.0), &game), Some(Point2d::new(3, 3)));
+
+    let mut patrol = Patrol::new(vec![walled_off]);
+    patrol.idle(position, &game);
+    patrol.unreachable(walled_off);
+    assert_eq!(patrol.idle(position, &game), None);
+
+    let mut wander = Wander::default();
+    let destination = wander.idle(position, &game).unwrap();
+    wander.unreachable(walled_off);
+    assert_eq!(wander.idle(position, &game), Some(destination));
+    wander.unreachable(destination);
+    assert_ne!(wander.idle(position, &game), Some(destination));
+
+    // the enemy finds out on its own, and walks on to the next waypoint
+    let mut enemy = Enemy::with_speed(10.0)
+        .with_vision(0.0, 0.0)
+        .with_behavior(Patrol::new(vec![walled_off, Point2d::new(3, 3)]));
+    enemy.set_position(position);
+    for _ in 0..10 {
+        enemy.update(&game);
+    }
+    assert_eq!(enemy.position(), Point2d::new(3.0, 3.0));
+}
+
+#[test]
+fn test_flee_runs_away_from_a_shielded_player() {
+    let game = game();
+    let position = Point2d::new(4.0, 4.0);
+    assert_eq!(Flee.pursue(position, 0, &game), Some(Point2d::new(2, 2)));
+
+    game.player_state(0).borrow_mut().collect(CollectibleKind::Shield);
+    assert_eq!(Flee.pursue(position, 0, &game), Some(Point2d::new(5, 5)));
+}
+
+#[test]
+fn test_walls_hide_the_player() {
+    // a wall splits the game in two halves
+    let game = game_with_walls((1..9).map(|y| Wall::new(5, y)).collect());
+    let mut enemy = Enemy::with_speed(1.0);
+    enemy.facing = Point2d::new(-1.0, 0.0);
+
+    enemy.set_position(Point2d::new(4.0, 4.0));
+    assert!(enemy.sees_player(0, &game));
+
+    enemy.set_position(Point2d::new(7.0, 2.0));
+    assert!(!enemy.sees_player(0, &game));
+
+    // out of the view cone
+    enemy.set_position(Point2d::new(4.0, 4.0));
+    enemy.facing = Point2d::new(1.0, 0.0);
+    assert!(!enemy.sees_player(0, &game));
+}
+
+#[test]
+fn test_enemy_investigates_and_searches_before_giving_up() {
+    let game = game();
+    let last_known = Point2d::new(2, 2);
+    let mut awareness = Awareness::default();
+
+    awareness.update(Some(last_known), Point2d::new(6, 6), &game);
+    assert_eq!(awareness, Awareness::Tracking { last_known });
+
+    awareness.update(None, Point2d::new(5, 5), &game);
+    assert_eq!(awareness, Awareness::Investigating { last_known });
+
+    awareness.update(None, last_known, &game);
+    assert!(matches!(awareness, Awareness::Searching { around, .. } if around == last_known));
+
+    for _ in 0..100 {
+        awareness.update(None, last_known, &game);
+    }
+    assert_eq!(awareness, Awareness::Unaware);
+
+    awareness.update(Some(last_known), Point2d::new(6, 6), &game);
+    assert_eq!(awareness, Awareness::Tracking { last_known });
+}
+
+#[test]
+fn test_enemy_searches_when_the_last_known_position_can_not_be_reached() {
+    let game = game_with_walled_off_tile();
+    let last_known = Point2d::new(7, 7);
+    let mut awareness = Awareness::Investigating { last_known };
+
+    awareness.unreachable(Point2d::new(3, 3), &game);
+    assert_eq!(awareness, Awareness::Investigating { last_known });
+    awareness.unreachable(last_known, &game);
+    assert!(matches!(awareness, Awareness::Searching { around, .. } if around == last_known));
+
+    // the enemy finds out on its own, and gives up after searching
+    let mut enemy = Enemy::with_speed(1.0).with_vision(0.0, 0.0);
+    enemy.set_position(Point2d::new(4.0, 4.0));
+    enemy.awareness = Awareness::Investigating { last_known };
+    enemy.update(&game);
+    assert!(matches!(enemy.awareness(), Awareness::Searching { .. }));
+    for _ in 0..100 {
+        enemy.update(&game);
+    }
+    assert_eq!(enemy.awareness(), &Awareness::Unaware);
+}
+
+#[test]
+fn test_enemy_goes_after_the_nearest_visible_player() {
+    let mut game = Game::builder()
+        .width(10)
+        .height(10)
+        .enemies(vec![])
+        .n_players(2)
+        .player_starting_position(2.0, 2.0)
+        .seed(1)
+        .renderer(Box::new(GridRenderer::new(10, 13)))
+        .build();
+    game.init();
+    // the second player starts next to the first one
+    assert_eq!(game.player_position(1).round().to_u16(), Point2d::new(3, 2));
+
+    let mut enemy = Enemy::with_speed(1.0);
+    enemy.facing = Point2d::new(-1.0, 0.0);
+    enemy.set_position(Point2d::new(7.0, 2.0));
+    assert_eq!(enemy.nearest_visible_player(&game), Some(1));
+
+    game.player_state(1)
+        .borrow_mut()
+        .collect(CollectibleKind::Invisibility);
+    assert_eq!(enemy.nearest_visible_player(&game), Some(0));
+
+    while game.player_state(0).borrow().is_alive() {
+        game.player_state(0).borrow_mut().decrease_health();
+    }
+    assert_eq!(enemy.nearest_visible_player(&game), None);
+}
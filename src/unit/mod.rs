@@ -3,9 +3,11 @@ mod enemy;
 mod player;
 mod wall;
 
-pub use collectible::Collectible;
+pub use collectible::{Collectible, CollectibleKind};
 pub use enemy::Enemy;
+pub use enemy::{Ambush, Behavior, Chase, EnemyBehavior, Flee, Patrol, Wander};
 pub use player::Player;
 pub use player::PlayerBuilder;
 pub use player::PlayerState;
-pub use wall::Wall;
+pub use player::{EffectKind, StatusEffect, StatusEffects};
+pub use wall::{Wall, Walls};
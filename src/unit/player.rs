@@ -1,13 +1,11 @@
-use std::{
-    f64::consts::PI,
-    fmt::{Display, Formatter},
-    time::Duration,
-};
+use std::{f64::consts::PI, time::Duration};
 
+use num::Zero;
 use serde::{Deserialize, Serialize};
 
 use crate::{
     game::Game,
+    input::ControlScheme,
     point::Point2d,
     traits::{Position, UpdatableByTimeFrame},
 };
@@ -15,29 +13,113 @@ use crate::{
 pub mod builder;
 pub use builder::PlayerBuilder;
 
+pub mod effects;
+pub use effects::{EffectKind, StatusEffect, StatusEffects};
+
 pub mod state;
 pub use state::PlayerState;
 
 #[derive(Debug, Default, Serialize, Deserialize, PartialEq)]
 pub struct Player {
+    /// Which player this is, counting from 0.
+    #[serde(default)]
+    number: usize,
     position: Point2d<f64>,
     direction: Point2d<f64>,
     speed: f64,
+    /// Whether the player is drawn highlighted this update, which flashes
+    /// them while they are invulnerable.
+    flash: bool,
+    #[serde(default)]
+    control: ControlScheme,
+    /// The directions whose keys are held, with the direct control scheme.
+    #[serde(skip)]
+    held: Vec<Point2d<f64>>,
 }
 
 impl Player {
     pub fn new(position: Point2d<f64>, direction: Point2d<f64>, speed: f64) -> Self {
         Self {
+            number: 0,
             position,
             direction,
             speed,
+            flash: false,
+            control: ControlScheme::default(),
+            held: vec![],
         }
     }
 
+    pub fn with_number(mut self, number: usize) -> Self {
+        self.number = number;
+        self
+    }
+
+    pub fn number(&self) -> usize {
+        self.number
+    }
+
+    pub fn with_control_scheme(mut self, control: ControlScheme) -> Self {
+        self.control = control;
+        self
+    }
+
     pub fn builder() -> PlayerBuilder {
         PlayerBuilder::new()
     }
 
+    pub fn is_flashing(&self) -> bool {
+        self.flash
+    }
+
+    pub fn direction(&self) -> Point2d<f64> {
+        self.direction
+    }
+
+    pub fn control_scheme(&self) -> ControlScheme {
+        self.control
+    }
+
+    /// Whether the player moves with the next update.
+    pub fn is_moving(&self) -> bool {
+        match self.control {
+            ControlScheme::Tank => self.speed > 0.0,
+            ControlScheme::Direct => !self.held_direction().is_zero(),
+        }
+    }
+
+    /// Starts moving towards `direction` while it is held, together with
+    /// the other held directions.
+    pub fn hold(&mut self, direction: Point2d<f64>) {
+        if !self.held.contains(&direction) {
+            self.held.push(direction);
+        }
+        self.face_held_direction();
+    }
+
+    pub fn release(&mut self, direction: Point2d<f64>) {
+        self.held.retain(|held| *held != direction);
+        self.face_held_direction();
+    }
+
+    /// The sum of the held directions, so that e.g. up and left move
+    /// diagonally and left and right cancel out.
+    fn held_direction(&self) -> Point2d<f64> {
+        let mut sum = Point2d::zero();
+        for direction in &self.held {
+            sum += *direction;
+        }
+        sum.normalize()
+    }
+
+    /// Keeps facing the last direction moved in once the keys are let go.
+    fn face_held_direction(&mut self) {
+        let direction = self.held_direction();
+        if !direction.is_zero() {
+            self.direction = direction;
+        }
+    }
+
     pub fn turn_left(&mut self) {
         let angle = PI / 4.0;
         self.direction = self.direction.rotate(angle);
@@ -61,7 +143,7 @@ impl Player {
 
 impl Position<f64> for Player {
     fn position(&self) -> Point2d<f64> {
-        self.position.clone()
+        self.position
     }
 
     fn set_position(&mut self, position: Point2d<f64>) {
@@ -69,41 +151,43 @@ impl Position<f64> for Player {
     }
 }
 
-impl Display for Player {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        use crossterm::style::Stylize;
-
-        let icon = if self.speed == 0.0 {
-            "•"
-        } else {
-            match self.direction.round().to_i16() {
-                Point2d { x: 0, y: -1 } => "↑",
-                Point2d { x: 1, y: -1 } => "↗",
-                Point2d { x: 1, y: 0 } => "→",
-                Point2d { x: 1, y: 1 } => "↘",
-                Point2d { x: 0, y: 1 } => "↓",
-                Point2d { x: -1, y: 1 } => "↙",
-                Point2d { x: -1, y: 0 } => "←",
-                Point2d { x: -1, y: -1 } => "↖",
-                _ => "•",
-            }
-        };
-        write!(f, "{}", icon.dark_blue())
+impl Player {
+    /// The direction the player moves in, in steps of 45° clockwise from
+    /// up, none while it stands still.
+    pub fn facing(&self) -> Option<usize> {
+        if !self.is_moving() {
+            return None;
+        }
+        match self.direction.round().to_i16() {
+            Point2d { x: 0, y: -1 } => Some(0),
+            Point2d { x: 1, y: -1 } => Some(1),
+            Point2d { x: 1, y: 0 } => Some(2),
+            Point2d { x: 1, y: 1 } => Some(3),
+            Point2d { x: 0, y: 1 } => Some(4),
+            Point2d { x: -1, y: 1 } => Some(5),
+            Point2d { x: -1, y: 0 } => Some(6),
+            Point2d { x: -1, y: -1 } => Some(7),
+            _ => None,
+        }
     }
 }
 
 impl Player {
-    fn forward_position(&self, since_last_time: &Duration) -> Point2d<f64> {
-        self.position + (self.direction * (self.speed * since_last_time.as_secs_f64()))
+    fn movement(&self, since_last_time: &Duration) -> Point2d<f64> {
+        if !self.is_moving() {
+            return Point2d::zero();
+        }
+        self.direction * (self.speed * since_last_time.as_secs_f64())
     }
 }
 
 impl UpdatableByTimeFrame for Player {
     fn update(&mut self, game: &Game) {
-        // move player if not colliding with a wall
-        let player_next_position = self.forward_position(&game.update_interval_millis);
-        if !game.do_walls_collide(player_next_position.round().to_u16()) {
-            self.position = player_next_position;
-        }
+        // move player, sliding along the walls in the way
+        let movement = self.movement(&game.update_interval_millis)
+            * game.player_state(self.number).borrow().speed_multiplier();
+        self.position = game.slide(self.position, movement);
+
+        self.flash = game.player_state(self.number).borrow().is_invulnerable() && !self.flash;
     }
 }
@@ -1,20 +1,25 @@
-use crate::point::Point2d;
+use crate::{input::ControlScheme, point::Point2d};
 
 use super::Player;
 
+#[derive(Clone)]
 pub struct PlayerBuilder {
+    number: usize,
     position: Point2d<f64>,
     direction: Point2d<f64>,
     speed: f64,
+    control: ControlScheme,
 }
 
 #[allow(clippy::new_without_default)]
 impl PlayerBuilder {
     pub fn new() -> Self {
         Self {
+            number: 0,
             position: Point2d::new(1.0, 1.0),
             direction: Point2d::new(1.0, 0.0),
             speed: 0.0,
+            control: ControlScheme::default(),
         }
     }
 
@@ -23,6 +28,10 @@ impl PlayerBuilder {
         self
     }
 
+    pub fn starting_position(&self) -> Point2d<f64> {
+        self.position
+    }
+
     pub fn direction(mut self, x: f64, y: f64) -> Self {
         self.direction = Point2d::new(x, y);
         self
@@ -33,11 +42,19 @@ impl PlayerBuilder {
         self
     }
 
+    pub fn number(mut self, number: usize) -> Self {
+        self.number = number;
+        self
+    }
+
+    pub fn control_scheme(mut self, control: ControlScheme) -> Self {
+        self.control = control;
+        self
+    }
+
     pub fn build(self) -> Player {
-        Player {
-            position: self.position,
-            direction: self.direction,
-            speed: self.speed,
-        }
+        Player::new(self.position, self.direction, self.speed)
+            .with_number(self.number)
+            .with_control_scheme(self.control)
     }
 }
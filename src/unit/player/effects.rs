@@ -0,0 +1,155 @@
+mod tests;
+
+use std::fmt::{self, Display, Formatter};
+
+use serde::{Deserialize, Serialize};
+
+/// Something that affects the player for a number of updates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum EffectKind {
+    /// Doubles the speed of the player.
+    SpeedBoost,
+    /// Halves the speed of the player, once per stack.
+    Slow,
+    /// Hides the player from the enemies.
+    Invisibility,
+    /// Stops the enemies.
+    Freeze,
+    /// Protects the player from the enemies.
+    Shield,
+    /// Keeps the enemies from hurting the player right after they did.
+    Invulnerability,
+    /// Takes one health every `POISON_INTERVAL` updates, once per stack.
+    Poison,
+}
+
+/// How often poison takes health, in updates.
+pub const POISON_INTERVAL: u32 = 10;
+
+/// What happens when an effect is applied while it is still active.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stacking {
+    /// The effect lasts as long as the longer of the two.
+    Refresh,
+    /// The durations add up.
+    Extend,
+    /// The effect gets stronger, up to `max` stacks, and starts over.
+    Stack { max: u8 },
+}
+
+impl EffectKind {
+    pub fn stacking(self) -> Stacking {
+        match self {
+            EffectKind::SpeedBoost
+            | EffectKind::Invisibility
+            | EffectKind::Shield
+            | EffectKind::Invulnerability => Stacking::Refresh,
+            EffectKind::Freeze => Stacking::Extend,
+            EffectKind::Slow | EffectKind::Poison => Stacking::Stack { max: 3 },
+        }
+    }
+}
+
+impl Display for EffectKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            EffectKind::SpeedBoost => "Speed",
+            EffectKind::Slow => "Slow",
+            EffectKind::Invisibility => "Invisible",
+            EffectKind::Freeze => "Freeze",
+            EffectKind::Shield => "Shield",
+            EffectKind::Invulnerability => "Invulnerable",
+            EffectKind::Poison => "Poison",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct StatusEffect {
+    kind: EffectKind,
+    ticks_left: u32,
+    stacks: u8,
+}
+
+impl StatusEffect {
+    pub fn kind(&self) -> EffectKind {
+        self.kind
+    }
+
+    pub fn ticks_left(&self) -> u32 {
+        self.ticks_left
+    }
+
+    pub fn stacks(&self) -> u8 {
+        self.stacks
+    }
+}
+
+/// The effects that are active on the player, at most one per kind, in the
+/// order they were applied.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StatusEffects {
+    active: Vec<StatusEffect>,
+}
+
+impl StatusEffects {
+    /// Applies `kind` for `ticks` updates, combining it with an active
+    /// effect of the same kind according to its stacking rule.
+    pub fn apply(&mut self, kind: EffectKind, ticks: u32) {
+        if ticks == 0 {
+            return;
+        }
+        let Some(effect) = self.active.iter_mut().find(|effect| effect.kind == kind) else {
+            self.active.push(StatusEffect {
+                kind,
+                ticks_left: ticks,
+                stacks: 1,
+            });
+            return;
+        };
+        match kind.stacking() {
+            Stacking::Refresh => effect.ticks_left = effect.ticks_left.max(ticks),
+            Stacking::Extend => effect.ticks_left += ticks,
+            Stacking::Stack { max } => {
+                effect.stacks = (effect.stacks + 1).min(max);
+                effect.ticks_left = ticks;
+            }
+        }
+    }
+
+    pub fn remove(&mut self, kind: EffectKind) {
+        self.active.retain(|effect| effect.kind != kind);
+    }
+
+    pub fn get(&self, kind: EffectKind) -> Option<&StatusEffect> {
+        self.active.iter().find(|effect| effect.kind == kind)
+    }
+
+    pub fn is_active(&self, kind: EffectKind) -> bool {
+        self.get(kind).is_some()
+    }
+
+    /// How many times `kind` is stacked, zero when it is not active.
+    pub fn stacks(&self, kind: EffectKind) -> u8 {
+        self.get(kind).map_or(0, |effect| effect.stacks)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &StatusEffect> {
+        self.active.iter()
+    }
+
+    /// Counts every effect down by one update and returns the ones that
+    /// wore off.
+    pub fn update(&mut self) -> Vec<StatusEffect> {
+        self.active
+            .iter_mut()
+            .for_each(|effect| effect.ticks_left -= 1);
+        let (expired, active) = self
+            .active
+            .iter()
+            .partition(|effect| effect.ticks_left == 0);
+        self.active = active;
+        expired
+    }
+}
@@ -0,0 +1,76 @@
+#![cfg(test)]
+
+use crate::unit::PlayerState;
+
+use super::*;
+
+#[test]
+fn test_effects_stack_by_their_rules() {
+    let mut effects = StatusEffects::default();
+
+    effects.apply(EffectKind::Shield, 10);
+    effects.apply(EffectKind::Shield, 5);
+    assert_eq!(effects.get(EffectKind::Shield).unwrap().ticks_left(), 10);
+
+    effects.apply(EffectKind::Freeze, 10);
+    effects.apply(EffectKind::Freeze, 5);
+    assert_eq!(effects.get(EffectKind::Freeze).unwrap().ticks_left(), 15);
+
+    for _ in 0..4 {
+        effects.apply(EffectKind::Poison, 7);
+    }
+    assert_eq!(effects.stacks(EffectKind::Poison), 3);
+    assert_eq!(effects.get(EffectKind::Poison).unwrap().ticks_left(), 7);
+
+    assert_eq!(effects.iter().count(), 3);
+    assert_eq!(effects.stacks(EffectKind::Slow), 0);
+}
+
+#[test]
+fn test_effects_expire() {
+    let mut effects = StatusEffects::default();
+    effects.apply(EffectKind::Slow, 1);
+    effects.apply(EffectKind::Invisibility, 2);
+
+    let expired = effects.update();
+    assert_eq!(expired.len(), 1);
+    assert_eq!(expired[0].kind(), EffectKind::Slow);
+    assert!(!effects.is_active(EffectKind::Slow));
+    assert!(effects.is_active(EffectKind::Invisibility));
+
+    let expired = effects.update();
+    assert_eq!(expired[0].kind(), EffectKind::Invisibility);
+    assert_eq!(effects.iter().count(), 0);
+    assert!(effects.update().is_empty());
+}
+
+#[test]
+fn test_player_state_consults_the_effects() {
+    let mut state = PlayerState::new(10, 0);
+    state.apply_effect(EffectKind::Slow, 20);
+    state.apply_effect(EffectKind::Slow, 20);
+    assert_eq!(state.speed_multiplier(), 0.25);
+    state.apply_effect(EffectKind::SpeedBoost, 20);
+    assert_eq!(state.speed_multiplier(), 0.5);
+
+    state.apply_effect(EffectKind::Shield, 5);
+    assert!(!state.can_be_hurt());
+
+    state.apply_effect(EffectKind::Poison, 2 * POISON_INTERVAL);
+    state.apply_effect(EffectKind::Poison, 2 * POISON_INTERVAL);
+    let expired: Vec<EffectKind> = (0..2 * POISON_INTERVAL)
+        .flat_map(|_| state.update())
+        .map(|effect| effect.kind())
+        .collect();
+    assert_eq!(state.health(), 6);
+    assert_eq!(
+        expired,
+        vec![
+            EffectKind::Shield,
+            EffectKind::Slow,
+            EffectKind::SpeedBoost,
+            EffectKind::Poison
+        ]
+    );
+    assert!(state.can_be_hurt());
+}
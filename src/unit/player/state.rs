@@ -1,20 +1,36 @@
 use serde::{Deserialize, Serialize};
 
+use crate::unit::CollectibleKind;
+
+use super::effects::{EffectKind, StatusEffect, StatusEffects, POISON_INTERVAL};
 
 #[derive(Debug, Default, Serialize, Deserialize, PartialEq)]
 pub struct PlayerState {
     health: u8,
+    /// The health the player started with, which hearts restore up to.
+    max_health: u8,
     score: u32,
+    effects: StatusEffects,
+    /// How many updates the player can not be hurt after an enemy hit.
+    invulnerability_ticks: u32,
 }
 
 impl PlayerState {
-    pub fn new(health: u8, score: u32,) -> Self {
+    pub fn new(health: u8, score: u32) -> Self {
         Self {
             health,
+            max_health: health,
             score,
+            ..Self::default()
         }
     }
 
+    /// Keeps the player from being hurt for `ticks` updates after a hit.
+    pub fn with_invulnerability(mut self, ticks: u32) -> Self {
+        self.invulnerability_ticks = ticks;
+        self
+    }
+
     pub fn is_alive(&self) -> bool {
         self.health > 0
     }
@@ -34,4 +50,83 @@ impl PlayerState {
     pub fn increase_score(&mut self) {
         self.score += 1;
     }
-}
\ No newline at end of file
+
+    pub fn effects(&self) -> &StatusEffects {
+        &self.effects
+    }
+
+    /// Applies `kind` for the next `ticks` updates.
+    pub fn apply_effect(&mut self, kind: EffectKind, ticks: u32) {
+        self.effects.apply(kind, ticks);
+    }
+
+    /// Applies the effect of a collectible the player picked up.
+    pub fn collect(&mut self, kind: CollectibleKind) {
+        match kind {
+            CollectibleKind::Coin => self.increase_score(),
+            CollectibleKind::Heart => {
+                self.health = self.health.saturating_add(1).min(self.max_health)
+            }
+            _ => {}
+        }
+        if let Some(effect) = kind.effect() {
+            self.apply_effect(effect, kind.duration());
+        }
+    }
+
+    /// How many times faster than usual the player moves.
+    pub fn speed_multiplier(&self) -> f64 {
+        let boost = if self.effects.is_active(EffectKind::SpeedBoost) {
+            2.0
+        } else {
+            1.0
+        };
+        boost * 0.5_f64.powi(self.effects.stacks(EffectKind::Slow).into())
+    }
+
+    pub fn is_invisible(&self) -> bool {
+        self.effects.is_active(EffectKind::Invisibility)
+    }
+
+    pub fn are_enemies_frozen(&self) -> bool {
+        self.effects.is_active(EffectKind::Freeze)
+    }
+
+    pub fn is_shielded(&self) -> bool {
+        self.effects.is_active(EffectKind::Shield)
+    }
+
+    pub fn is_invulnerable(&self) -> bool {
+        self.effects.is_active(EffectKind::Invulnerability)
+    }
+
+    /// Whether an enemy that catches the player takes health.
+    pub fn can_be_hurt(&self) -> bool {
+        !self.is_shielded() && !self.is_invulnerable()
+    }
+
+    /// An enemy caught the player. Takes one health if the player can be
+    /// hurt and makes them invulnerable for a while. Returns whether the
+    /// player was hurt.
+    pub fn hit(&mut self) -> bool {
+        if !self.can_be_hurt() {
+            return false;
+        }
+        self.decrease_health();
+        self.apply_effect(EffectKind::Invulnerability, self.invulnerability_ticks);
+        true
+    }
+
+    /// Applies the active effects for one update and counts them down.
+    /// Returns the effects that wore off.
+    pub fn update(&mut self) -> Vec<StatusEffect> {
+        if let Some(poison) = self.effects.get(EffectKind::Poison) {
+            if poison.ticks_left() % POISON_INTERVAL == 0 {
+                for _ in 0..poison.stacks() {
+                    self.decrease_health();
+                }
+            }
+        }
+        self.effects.update()
+    }
+}
@@ -1,6 +1,12 @@
+mod tests;
+
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+
 use crate::{point::Point2d, traits::Position};
 
-#[derive(Debug, Default, serde::Deserialize, serde::Serialize, PartialEq)]
+#[derive(Debug, Default, Clone, serde::Deserialize, serde::Serialize, PartialEq)]
 pub struct Wall {
     position: Point2d<u16>,
 }
@@ -22,3 +28,73 @@ impl Position<u16> for Wall {
         self.position.y = position.y;
     }
 }
+
+/// The walls of the game, indexed by their position so that checking a tile
+/// does not scan every wall. Serialized as a plain list of walls.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(from = "Vec<Wall>", into = "Vec<Wall>")]
+pub struct Walls {
+    walls: Vec<Wall>,
+    positions: HashSet<Point2d<u16>>,
+}
+
+impl Walls {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `wall` unless there already is one at its position. Returns
+    /// whether it was added.
+    pub fn push(&mut self, wall: Wall) -> bool {
+        let added = self.positions.insert(wall.position());
+        if added {
+            self.walls.push(wall);
+        }
+        added
+    }
+
+    pub fn contains(&self, position: Point2d<u16>) -> bool {
+        self.positions.contains(&position)
+    }
+
+    pub fn len(&self) -> usize {
+        self.walls.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.walls.is_empty()
+    }
+
+    /// The walls in the order they were added.
+    pub fn iter(&self) -> impl Iterator<Item = &Wall> {
+        self.walls.iter()
+    }
+}
+
+impl Extend<Wall> for Walls {
+    fn extend<I: IntoIterator<Item = Wall>>(&mut self, walls: I) {
+        walls.into_iter().for_each(|wall| {
+            self.push(wall);
+        });
+    }
+}
+
+impl FromIterator<Wall> for Walls {
+    fn from_iter<I: IntoIterator<Item = Wall>>(walls: I) -> Self {
+        let mut result = Self::new();
+        result.extend(walls);
+        result
+    }
+}
+
+impl From<Vec<Wall>> for Walls {
+    fn from(walls: Vec<Wall>) -> Self {
+        walls.into_iter().collect()
+    }
+}
+
+impl From<Walls> for Vec<Wall> {
+    fn from(walls: Walls) -> Self {
+        walls.walls
+    }
+}
@@ -0,0 +1,28 @@
+#![cfg(test)]
+
+use crate::point::Point2d;
+
+use super::{Wall, Walls};
+
+#[test]
+fn test_walls_index_positions() {
+    let mut walls = Walls::from(vec![Wall::new(1, 2), Wall::new(3, 4)]);
+    assert!(walls.contains(Point2d::new(1, 2)));
+    assert!(!walls.contains(Point2d::new(2, 1)));
+
+    assert!(walls.push(Wall::new(2, 1)));
+    assert!(walls.contains(Point2d::new(2, 1)));
+    assert!(!walls.push(Wall::new(1, 2)));
+    assert_eq!(walls.len(), 3);
+}
+
+#[test]
+fn test_walls_serialize_as_a_list() {
+    let walls = Walls::from(vec![Wall::new(1, 2), Wall::new(3, 4)]);
+    let json = serde_json::to_string(&walls).unwrap();
+    assert_eq!(json, r#"[{"position":{"x":1,"y":2}},{"position":{"x":3,"y":4}}]"#);
+
+    let deserialized: Walls = serde_json::from_str(&json).unwrap();
+    assert_eq!(deserialized, walls);
+    assert!(deserialized.contains(Point2d::new(3, 4)));
+}
@@ -0,0 +1,65 @@
+mod tests;
+
+use crate::point::Point2d;
+
+/// Whether nothing blocks the straight line between the centers of two
+/// tiles. The line is walked with Bresenham's algorithm, and the two end
+/// tiles themselves are not checked.
+pub fn line_of_sight(
+    from: Point2d<u16>,
+    to: Point2d<u16>,
+    is_blocked: impl Fn(Point2d<u16>) -> bool,
+) -> bool {
+    if from == to {
+        return true;
+    }
+    let (x1, y1) = (to.x as i32, to.y as i32);
+    let (mut x, mut y) = (from.x as i32, from.y as i32);
+    let dx = (x1 - x).abs();
+    let dy = -(y1 - y).abs();
+    let step_x = if x < x1 { 1 } else { -1 };
+    let step_y = if y < y1 { 1 } else { -1 };
+    let mut error = dx + dy;
+
+    loop {
+        let doubled = 2 * error;
+        if doubled >= dy {
+            error += dy;
+            x += step_x;
+        }
+        if doubled <= dx {
+            error += dx;
+            y += step_y;
+        }
+        if (x, y) == (x1, y1) {
+            return true;
+        }
+        if is_blocked(Point2d::new(x as u16, y as u16)) {
+            return false;
+        }
+    }
+}
+
+/// Whether `target` lies within `view_distance` of `eye` and inside the
+/// cone of `field_of_view` degrees around `facing`. An eye that does not
+/// face anywhere looks all around.
+pub fn in_view_cone(
+    eye: Point2d<f64>,
+    facing: Point2d<f64>,
+    target: Point2d<f64>,
+    view_distance: f64,
+    field_of_view: f64,
+) -> bool {
+    let distance = eye.distance(&target);
+    if distance > view_distance {
+        return false;
+    }
+    let facing = facing.normalize();
+    if distance == 0.0 || (facing.x == 0.0 && facing.y == 0.0) || field_of_view >= 360.0 {
+        return true;
+    }
+
+    let to_target = (target - eye).normalize();
+    let cos = facing.x * to_target.x + facing.y * to_target.y;
+    cos >= (field_of_view / 2.0).to_radians().cos()
+}
@@ -0,0 +1,31 @@
+#![cfg(test)]
+
+use crate::point::Point2d;
+
+use super::*;
+
+#[test]
+fn test_walls_block_the_line_of_sight() {
+    let wall = Point2d::new(3, 2);
+    let is_blocked = |position: Point2d<u16>| position == wall;
+
+    assert!(!line_of_sight(Point2d::new(1, 2), Point2d::new(6, 2), is_blocked));
+    assert!(!line_of_sight(Point2d::new(6, 1), Point2d::new(1, 3), is_blocked));
+    assert!(line_of_sight(Point2d::new(1, 1), Point2d::new(6, 1), is_blocked));
+    assert!(line_of_sight(Point2d::new(3, 5), Point2d::new(3, 3), is_blocked));
+    // the end tiles are not checked
+    assert!(line_of_sight(Point2d::new(2, 2), wall, is_blocked));
+    assert!(line_of_sight(wall, wall, is_blocked));
+}
+
+#[test]
+fn test_view_cone() {
+    let eye = Point2d::new(5.0, 5.0);
+    let right = Point2d::new(1.0, 0.0);
+
+    assert!(in_view_cone(eye, right, Point2d::new(9.0, 6.0), 10.0, 90.0));
+    assert!(!in_view_cone(eye, right, Point2d::new(5.0, 9.0), 10.0, 90.0));
+    assert!(!in_view_cone(eye, right, Point2d::new(1.0, 5.0), 10.0, 90.0));
+    assert!(!in_view_cone(eye, right, Point2d::new(20.0, 5.0), 10.0, 90.0));
+    assert!(in_view_cone(eye, right, Point2d::new(1.0, 5.0), 10.0, 360.0));
+}